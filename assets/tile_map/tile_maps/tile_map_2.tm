@@ -1,7 +1,7 @@
-0 0 0 0 0 0 0 0 0
-0 0 0 0 0 0 0 0 0
-0 0 1 1 1 1 0 0 0
-0 1 1 1 1 1 1 1 1
-0 1 1 1 1 1 1 1 1
+0 0 0 m m 0 0 0 0
+0 p 0 0 h 0 0 5 0
+0 0 1 1 1 3 0 0 0
+0 1 1 1 1 1 1 1 4
+0 1 1 1 1 1 1 1 4
 0 0 0 1 1 1 1 1 1
-0 0 0 0 0 0 0 0 0
+0 6 x w w w u c 0
@@ -13,6 +13,7 @@ use sdl2::{
 
 pub mod collision_system;
 pub mod effect_system;
+pub mod event_system;
 pub mod game_object;
 pub mod turn_system;
 
@@ -22,10 +23,17 @@ pub mod input;
 
 pub mod game_info;
 
+pub mod campaign;
+pub mod match_info;
+pub mod match_settings;
+pub mod scenario;
+
 pub mod game_state;
 
 pub mod scene_manager;
 
+pub mod registry;
+
 pub mod menu;
 
 pub mod button;
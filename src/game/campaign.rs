@@ -0,0 +1,472 @@
+use std::{collections::HashMap, fs, io, path::Path, rc::Rc};
+
+use sdl2::render::Texture;
+
+use crate::game::{
+    effect_system::effects::{
+        self_effect::{BulwarkEffect, FighterEffect, MendingEffect, ScoutEffect},
+        Effect,
+    },
+    game_object::game_objects::bud::{
+        ai::Difficulty,
+        bud_data::{InitialBudData, TRAIT_SLOTS},
+        weapon::{WeaponEnum, WeaponInfo},
+    },
+    match_settings::MatchSettings,
+    turn_system::game_modes::death_match::DeathMatch,
+};
+
+const CAMPAIGN_FILE: &str = "assets/campaign/campaign.txt";
+const BATTLE_DIR: &str = "assets/campaign/battles";
+pub const SAVE_DIR: &str = "saves";
+const SAVE_FILE: &str = "saves/campaign.txt";
+
+/// The `key: value` pairs of a data file, skipping blank lines and `#` comments
+pub fn read_pairs(file: impl AsRef<Path>) -> io::Result<Vec<(String, String)>> {
+    Ok(fs::read_to_string(file)?
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .filter_map(|line| {
+            line.split_once(':')
+                .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect())
+}
+
+/// The traits a campaign can hand out, by the lowercase name their icons go by
+fn make_trait<'g>(name: &str) -> Option<Box<dyn Effect<'g>>> {
+    match name {
+        "fighter" => Some(Box::new(FighterEffect::new())),
+        "bulwark" => Some(Box::new(BulwarkEffect::new())),
+        "scout" => Some(Box::new(ScoutEffect::new())),
+        "mending" => Some(Box::new(MendingEffect::new())),
+        _ => None,
+    }
+}
+
+pub fn weapon_index(name: &str) -> Option<u8> {
+    (0..8).find(|index| WeaponEnum::get_weapon(*index).1 == name)
+}
+
+/// What it takes to win a battle
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Victory {
+    /// Defeat every enemy
+    Rout,
+    /// The first side to lose this many buds loses
+    Kills(u8),
+    /// Each side's first bud leads it, the side that loses its leader loses
+    Leaders,
+}
+
+impl Victory {
+    pub fn from_value(value: &str) -> Option<Victory> {
+        let mut words = value.split_whitespace();
+        match words.next()? {
+            "rout" => Some(Victory::Rout),
+            "kills" => words.next()?.parse().ok().map(Victory::Kills),
+            "leaders" => Some(Victory::Leaders),
+            _ => None,
+        }
+    }
+    pub fn get_description(&self) -> String {
+        match self {
+            Victory::Rout => String::from("Defeat every enemy"),
+            Victory::Kills(kills) => format!("Take down {kills} enemies before losing as many"),
+            Victory::Leaders => {
+                String::from("Take down the enemy leader, keep your first bud alive")
+            }
+        }
+    }
+    pub fn make_mode(&self, team_sizes: Vec<u8>) -> DeathMatch {
+        match self {
+            Victory::Rout => DeathMatch::new(u8::MAX, Vec::new(), team_sizes),
+            Victory::Kills(kills) => DeathMatch::new(*kills, Vec::new(), team_sizes),
+            Victory::Leaders => DeathMatch::new(u8::MAX, vec![0], team_sizes),
+        }
+    }
+}
+
+/// Changes to the usual arena a battle can ask for
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Rule {
+    /// The arena starts closing in after this many rounds
+    SuddenDeath(u32),
+    /// Neither the map nor the game mode puts down pickups
+    NoPickups,
+}
+
+impl Rule {
+    fn from_value(value: &str) -> Option<Rule> {
+        let mut words = value.split_whitespace();
+        match words.next()? {
+            "sudden_death" => words.next()?.parse().ok().map(Rule::SuddenDeath),
+            "no_pickups" => Some(Rule::NoPickups),
+            _ => None,
+        }
+    }
+    pub fn get_description(&self) -> String {
+        match self {
+            Rule::SuddenDeath(round) => format!("The arena closes in after round {round}"),
+            Rule::NoPickups => String::from("No pickups"),
+        }
+    }
+}
+
+/// A bud as it is kept between battles, without any textures
+#[derive(Clone, Debug)]
+pub struct SavedBud {
+    pub name: String,
+    pub weapon: u8,
+    pub upgrades: u8,
+    pub level: u8,
+    pub xp: u32,
+    pub kills: u32,
+    pub damage: u32,
+    pub rounds: u64,
+    pub max_health: u16,
+    pub max_speed: u16,
+    pub slots: usize,
+    /// Lowercase trait names, one per filled slot
+    pub traits: Vec<String>,
+}
+
+impl SavedBud {
+    /// A fresh bud, as enemies and recruits start out
+    pub fn recruit(name: String, weapon: u8, traits: Vec<String>) -> Self {
+        Self {
+            name,
+            weapon,
+            upgrades: 0,
+            level: 1,
+            xp: 0,
+            kills: 0,
+            damage: 0,
+            rounds: 0,
+            max_health: 10,
+            max_speed: 3,
+            slots: TRAIT_SLOTS,
+            traits,
+        }
+    }
+    pub fn new(initial_bud_data: &InitialBudData) -> Self {
+        let weapon = &initial_bud_data.weapon_info.weapon;
+        Self {
+            name: initial_bud_data.name.clone(),
+            weapon: weapon.weapon_enum.get_index(),
+            upgrades: weapon.get_upgrades(),
+            level: initial_bud_data.level,
+            xp: initial_bud_data.xp,
+            kills: initial_bud_data.kills,
+            damage: initial_bud_data.damage_dealt,
+            rounds: initial_bud_data.rounds,
+            max_health: initial_bud_data.max_health,
+            max_speed: initial_bud_data.max_speed,
+            slots: initial_bud_data.effects.len(),
+            traits: initial_bud_data
+                .effects
+                .iter()
+                .flatten()
+                .map(|effect| effect.name().to_lowercase())
+                .collect(),
+        }
+    }
+    /// The bud ready for a battle, with its textures looked up by name. `None` when its weapon
+    /// has no icon, so a broken asset folder skips the bud instead of crashing
+    pub fn to_initial<'g>(
+        &self,
+        team: u8,
+        index: u8,
+        texture: Rc<Texture<'g>>,
+        icons: &HashMap<String, Rc<Texture<'g>>>,
+    ) -> Option<InitialBudData<'g>> {
+        let (mut weapon, weapon_string, weapon_rect) = WeaponEnum::get_weapon(self.weapon);
+        for _ in 0..self.upgrades {
+            weapon.upgrade();
+        }
+        let Some(weapon_tex) = icons.get(&weapon_string).cloned() else {
+            println!(
+                "Skipping {}, there is no icon for the {weapon_string}",
+                self.name
+            );
+            return None;
+        };
+        let mut initial_bud_data = InitialBudData::new(
+            texture,
+            team,
+            index,
+            self.name.clone(),
+            WeaponInfo::new(weapon, weapon_tex, weapon_rect),
+        );
+        initial_bud_data.level = self.level;
+        initial_bud_data.xp = self.xp;
+        initial_bud_data.kills = self.kills;
+        initial_bud_data.damage_dealt = self.damage;
+        initial_bud_data.rounds = self.rounds;
+        initial_bud_data.max_health = self.max_health;
+        initial_bud_data.max_speed = self.max_speed;
+        while initial_bud_data.effects.len() < self.slots && initial_bud_data.add_trait_slot() {}
+        for name in self.traits.iter() {
+            if let Some(effect) = make_trait(name) {
+                initial_bud_data.add_effect(effect, icons.get(name).cloned());
+            }
+        }
+        Some(initial_bud_data)
+    }
+    /// Written as `name|weapon|upgrades|level|xp|kills|damage|rounds|health|speed|slots|traits`
+    fn to_line(&self) -> String {
+        format!(
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+            self.name,
+            self.weapon,
+            self.upgrades,
+            self.level,
+            self.xp,
+            self.kills,
+            self.damage,
+            self.rounds,
+            self.max_health,
+            self.max_speed,
+            self.slots,
+            self.traits.join(",")
+        )
+    }
+    fn from_line(line: &str) -> Option<Self> {
+        let parts: Vec<&str> = line.split('|').collect();
+        let [name, weapon, upgrades, level, xp, kills, damage, rounds, max_health, max_speed, slots, traits] =
+            parts[..]
+        else {
+            return None;
+        };
+        Some(Self {
+            name: name.to_string(),
+            weapon: weapon.parse().ok().filter(|weapon| *weapon < 8)?,
+            upgrades: upgrades.parse().ok()?,
+            level: level.parse().ok()?,
+            xp: xp.parse().ok()?,
+            kills: kills.parse().ok()?,
+            damage: damage.parse().ok()?,
+            rounds: rounds.parse().ok()?,
+            max_health: max_health.parse().ok()?,
+            max_speed: max_speed.parse().ok()?,
+            slots: slots.parse().ok()?,
+            traits: traits
+                .split(',')
+                .filter(|name| !name.is_empty())
+                .map(String::from)
+                .collect(),
+        })
+    }
+}
+
+/// One scripted fight of the campaign, read from a file in the battle folder
+#[derive(Clone, Debug)]
+pub struct Battle {
+    pub name: String,
+    pub description: String,
+    /// Tile map file the battle is fought on
+    pub map: String,
+    pub difficulty: Difficulty,
+    pub victory: Victory,
+    pub rules: Vec<Rule>,
+    pub enemies: Vec<SavedBud>,
+    /// Traits the player can use from the next battle on, once this one is won
+    pub unlocks: Vec<String>,
+}
+
+impl Battle {
+    /// Enemies are written as `enemy: name, weapon, trait, trait`. `None` if the file can't be
+    /// read
+    fn load(file: &str) -> Option<Battle> {
+        let mut battle = Battle {
+            name: file.to_string(),
+            description: String::new(),
+            map: String::from("tile_map_2.tm"),
+            difficulty: Difficulty::Normal,
+            victory: Victory::Rout,
+            rules: Vec::new(),
+            enemies: Vec::new(),
+            unlocks: Vec::new(),
+        };
+        let pairs = match read_pairs(format!("{BATTLE_DIR}/{file}")) {
+            Ok(pairs) => pairs,
+            Err(e) => {
+                println!("Could not read battle {file}: {e}");
+                return None;
+            }
+        };
+        for (key, value) in pairs {
+            match key.as_str() {
+                "name" => battle.name = value,
+                "description" => battle.description = value,
+                "map" => battle.map = value,
+                "difficulty" => {
+                    battle.difficulty = Difficulty::from_name(&value).unwrap_or(battle.difficulty)
+                }
+                "victory" => battle.victory = Victory::from_value(&value).unwrap_or(battle.victory),
+                "rule" => battle.rules.extend(Rule::from_value(&value)),
+                "unlock" => battle.unlocks.push(value),
+                "enemy" => {
+                    let mut parts = value.split(',').map(str::trim);
+                    let name = parts.next().unwrap_or("Enemy").to_string();
+                    let weapon = parts.next().and_then(weapon_index).unwrap_or(0);
+                    let traits = parts.map(String::from).collect();
+                    battle.enemies.push(SavedBud::recruit(name, weapon, traits));
+                }
+                _ => println!("Unknown key {key} in battle {file}"),
+            }
+        }
+        Some(battle)
+    }
+    pub fn get_sudden_death_round(&self) -> Option<u32> {
+        self.rules.iter().find_map(|rule| match rule {
+            Rule::SuddenDeath(round) => Some(*round),
+            _ => None,
+        })
+    }
+    pub fn has_pickups(&self) -> bool {
+        !self.rules.contains(&Rule::NoPickups)
+    }
+}
+
+/// The ordered battles of the campaign and what the player starts with
+pub struct Campaign {
+    pub battles: Vec<Battle>,
+    /// The player's team is topped up with recruits to this size before every battle
+    pub squad: u8,
+    pub starting_traits: Vec<String>,
+}
+
+impl Campaign {
+    pub fn load() -> Campaign {
+        let mut campaign = Campaign {
+            battles: Vec::new(),
+            squad: 3,
+            starting_traits: Vec::new(),
+        };
+        let pairs = read_pairs(CAMPAIGN_FILE).unwrap_or_else(|e| {
+            println!("Could not read the campaign: {e}");
+            Vec::new()
+        });
+        for (key, value) in pairs {
+            match key.as_str() {
+                "squad" => campaign.squad = value.parse().unwrap_or(campaign.squad),
+                "trait" => campaign.starting_traits.push(value),
+                "battle" => campaign.battles.extend(Battle::load(&value)),
+                _ => println!("Unknown key {key} in the campaign"),
+            }
+        }
+        campaign
+    }
+}
+
+/// How far the player got, kept on disk between sessions
+#[derive(Clone, Debug)]
+pub struct CampaignProgress {
+    /// Battles won, the next battle to fight is the one at this index
+    pub completed: usize,
+    pub unlocked_traits: Vec<String>,
+    /// Buds that survived the last battle won
+    pub roster: Vec<SavedBud>,
+}
+
+impl CampaignProgress {
+    pub fn new(campaign: &Campaign) -> Self {
+        Self {
+            completed: 0,
+            unlocked_traits: campaign.starting_traits.clone(),
+            roster: Vec::new(),
+        }
+    }
+    /// The saved progress, or a fresh start if there is none or it can't be read
+    pub fn load(campaign: &Campaign) -> Self {
+        let mut progress = Self::new(campaign);
+        if !Path::new(SAVE_FILE).exists() {
+            return progress;
+        }
+        let pairs = match read_pairs(SAVE_FILE) {
+            Ok(pairs) => pairs,
+            Err(e) => {
+                println!("Could not read the campaign save, starting over: {e}");
+                return progress;
+            }
+        };
+        for (key, value) in pairs {
+            match key.as_str() {
+                "completed" => progress.completed = value.parse().unwrap_or(0),
+                "unlock" if !progress.unlocked_traits.contains(&value) => {
+                    progress.unlocked_traits.push(value)
+                }
+                "bud" => progress.roster.extend(SavedBud::from_line(&value)),
+                _ => {}
+            }
+        }
+        progress
+    }
+    pub fn save(&self) {
+        let mut lines = vec![format!("completed: {}", self.completed)];
+        lines.extend(
+            self.unlocked_traits
+                .iter()
+                .map(|name| format!("unlock: {name}")),
+        );
+        lines.extend(
+            self.roster
+                .iter()
+                .map(|bud| format!("bud: {}", bud.to_line())),
+        );
+        if let Err(e) =
+            fs::create_dir_all(SAVE_DIR).and_then(|_| fs::write(SAVE_FILE, lines.join("\n") + "\n"))
+        {
+            println!("Could not save the campaign: {e}");
+        }
+    }
+    pub fn is_unlocked(&self, name: &str) -> bool {
+        self.unlocked_traits.iter().any(|unlocked| unlocked == name)
+    }
+}
+
+/// The campaign battle being played, kept in `GameInfo` until the battle ends
+pub struct CampaignRun {
+    pub index: usize,
+    pub battle: Battle,
+    pub unlocked_traits: Vec<String>,
+    /// The settings from before the battle, put back once it is over
+    previous_settings: MatchSettings,
+}
+
+impl CampaignRun {
+    pub fn new(
+        index: usize,
+        battle: Battle,
+        unlocked_traits: Vec<String>,
+        previous_settings: MatchSettings,
+    ) -> Self {
+        Self {
+            index,
+            battle,
+            unlocked_traits,
+            previous_settings,
+        }
+    }
+    /// Gives up on the battle without saving anything
+    pub fn abandon(self) -> MatchSettings {
+        self.previous_settings
+    }
+    /// Saves what the battle changed, a win moves the campaign on and keeps the survivors, a
+    /// loss leaves the progress as it was so the battle can be tried again
+    pub fn finish(self, won: bool, survivors: &[InitialBudData]) -> MatchSettings {
+        let mut progress = CampaignProgress::load(&Campaign::load());
+        if won && self.index == progress.completed {
+            progress.completed += 1;
+            for name in self.battle.unlocks {
+                if !progress.is_unlocked(&name) {
+                    progress.unlocked_traits.push(name);
+                }
+            }
+            progress.roster = survivors.iter().map(SavedBud::new).collect();
+            progress.save();
+        }
+        self.previous_settings
+    }
+}
@@ -1,5 +1,6 @@
 extern crate sdl2;
 use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
 use std::f64::consts::PI;
 use std::i32;
 use std::ops::Not;
@@ -8,7 +9,11 @@ use std::rc::Rc;
 use sdl2::rect::Point;
 use sdl2::sys::SDL_atan2;
 
+use crate::game::creating_levels::terrain::Terrain;
 use crate::game::effect_system::effects::Effect;
+use crate::game::event_system::events::BudTag;
+use crate::game::game_object::game_objects::bud::{Direction, Flank};
+use crate::game::registry::ObjectId;
 use crate::vector2d::Vector2d;
 
 // pub enum ColliderType {
@@ -18,6 +23,29 @@ use crate::vector2d::Vector2d;
 
 pub trait Colliding<'g> {
     fn get_collider(&self) -> Point;
+    fn get_team(&self) -> Option<u8> {
+        None
+    }
+    fn get_tag(&self) -> Option<BudTag> {
+        None
+    }
+    fn get_facing(&self) -> Option<Direction> {
+        None
+    }
+    fn blocks_front(&self) -> bool {
+        false
+    }
+    /// Solid colliders block movement and shots, others can be stood on
+    fn is_solid(&self) -> bool {
+        true
+    }
+    fn get_tile_effects(&self) -> Vec<Box<dyn Effect<'g> + 'g>> {
+        Vec::new()
+    }
+    /// Effects for a bud stepping onto the tile, traps can spend themselves here
+    fn on_enter(&mut self) -> Vec<Box<dyn Effect<'g> + 'g>> {
+        Vec::new()
+    }
     fn on_effected(
         &mut self,
         effect: Box<dyn Effect<'g> + 'g>,
@@ -55,162 +83,207 @@ impl Not for Side {
     }
 }
 
+/// Colliders are indexed by id and by the grid tile they stand on,
+/// so tile lookups never have to borrow (or skip) the colliders themselves
 pub struct Collisions<'r> {
-    pub colliders: Vec<Rc<RefCell<dyn Colliding<'r> + 'r>>>,
+    colliders: BTreeMap<ObjectId, Rc<RefCell<dyn Colliding<'r> + 'r>>>,
+    positions: BTreeMap<ObjectId, Point>,
+    grid: HashMap<Point, Vec<ObjectId>>,
+    terrain: HashMap<Point, Terrain>,
 }
 
 impl<'r> Collisions<'r> {
-    pub fn new(colliders: Vec<Rc<RefCell<dyn Colliding<'r> + 'r>>>) -> Self {
-        Collisions { colliders }
+    pub fn new() -> Self {
+        Collisions {
+            colliders: BTreeMap::new(),
+            positions: BTreeMap::new(),
+            grid: HashMap::new(),
+            terrain: HashMap::new(),
+        }
     }
-    pub fn add(&mut self, col: Rc<RefCell<dyn Colliding<'r> + 'r>>) {
-        self.colliders.push(col);
+    pub fn add(&mut self, id: ObjectId, col: Rc<RefCell<dyn Colliding<'r> + 'r>>) {
+        let position = col.borrow().get_collider();
+        self.colliders.insert(id, col);
+        self.positions.insert(id, position);
+        self.grid.entry(position).or_default().push(id);
     }
     pub fn test(&self) {
         println!("Helllo");
     }
+    pub fn get_colliders(&self) -> Vec<Rc<RefCell<dyn Colliding<'r> + 'r>>> {
+        self.colliders.values().map(Rc::clone).collect()
+    }
+    pub fn get_collider(&self, id: ObjectId) -> Option<Rc<RefCell<dyn Colliding<'r> + 'r>>> {
+        self.colliders.get(&id).map(Rc::clone)
+    }
+    pub fn get_position(&self, id: ObjectId) -> Option<Point> {
+        self.positions.get(&id).copied()
+    }
 
-    /// Remove_all is prone to bugs; make sure collissions and scenemanager are on the same page; maybe incorporate the two
-    pub fn remove_all(&mut self, indexes: &[usize]) {
-        for (i, index) in indexes.iter().enumerate() {
-            self.colliders.remove(*index - i);
+    pub fn remove_all(&mut self, ids: &[ObjectId]) {
+        for id in ids {
+            self.remove(*id);
         }
     }
-    pub fn remove(&mut self, this: Point) {
-        println!("{}", self.colliders.len());
-        let index = self.colliders.iter().position(|col| {
-            if let Err(_) = col.try_borrow() {
-                return true;
-            } else {
-                return false;
-            }
-        });
-        if let Some(val) = index {
-            self.colliders.remove(val);
+    pub fn remove(&mut self, id: ObjectId) {
+        self.colliders.remove(&id);
+        if let Some(position) = self.positions.remove(&id) {
+            self.remove_from_grid(id, position);
         }
-
-        println!("{}", self.colliders.len());
     }
-    fn contains(a: &Rc<RefCell<Vec<Point>>>, b: &Rc<RefCell<Vec<Point>>>) -> bool {
-        true
+    /// Must be called whenever a collider changes tile, to keep the grid in sync
+    pub fn move_collider(&mut self, id: ObjectId, to: Point) {
+        if let Some(from) = self.positions.insert(id, to) {
+            self.remove_from_grid(id, from);
+        }
+        self.grid.entry(to).or_default().push(id);
     }
-    pub fn check_tile(&mut self, this: Point) -> bool {
-        for other in &self.colliders {
-            match other.try_borrow_mut() {
-                Ok(mut val) => {
-                    let other_col = val.get_collider();
-                    if other_col.x == this.x && other_col.y == this.y {
-                        return true;
-                    }
-                }
-                Err(_) => continue,
+    fn remove_from_grid(&mut self, id: ObjectId, position: Point) {
+        if let Some(ids) = self.grid.get_mut(&position) {
+            ids.retain(|other_id| *other_id != id);
+            if ids.is_empty() {
+                self.grid.remove(&position);
             }
         }
-        false
     }
 
-    pub fn impact_tile(&mut self, this: Point, effect: Box<dyn Effect<'r> + 'r>) -> bool {
-        for other in &self.colliders {
-            match other.try_borrow_mut() {
-                Ok(mut val) => {
-                    let other_col = val.get_collider();
-                    if other_col.x == this.x && other_col.y == this.y {
-                        val.on_effected(effect, self.colliders.clone());
-                        return true;
-                    }
-                }
-                Err(_) => continue,
+    pub fn set_terrain(&mut self, this: Point, terrain: Terrain) {
+        self.terrain.insert(this, terrain);
+    }
+    pub fn get_terrain(&self, this: Point) -> Terrain {
+        self.terrain.get(&this).copied().unwrap_or(Terrain::Plain)
+    }
+    /// Whether a bud of `weight` can step onto the tile
+    pub fn can_enter(&self, this: Point, weight: u8) -> bool {
+        !self.check_tile(this) && self.get_terrain(this).passable(weight)
+    }
+
+    /// Who is at the tile
+    pub fn get_at(&self, this: Point) -> &[ObjectId] {
+        self.grid
+            .get(&this)
+            .map(|ids| ids.as_slice())
+            .unwrap_or(&[])
+    }
+    /// Who is within `radius` tiles of `center`, counting diagonals as one tile
+    pub fn get_within(&self, center: Point, radius: i32) -> Vec<ObjectId> {
+        let mut ret = Vec::new();
+        for x in center.x - radius..=center.x + radius {
+            for y in center.y - radius..=center.y + radius {
+                ret.extend_from_slice(self.get_at(Point::new(x, y)));
             }
         }
-        false
+        ret
+    }
+    /// Whether something solid is on the tile, a collider that is borrowed right now counts as solid
+    pub fn check_tile(&self, this: Point) -> bool {
+        self.get_at(this).iter().any(|id| {
+            self.colliders
+                .get(id)
+                .is_some_and(|col| col.try_borrow().map_or(true, |col| col.is_solid()))
+        })
+    }
+    /// The colliders on the tile besides `except`, which the caller leaves out because it holds it
+    /// borrowed. Nothing else is borrowed during a lookup, so a conflict is a bug and panics
+    /// instead of passing for an empty tile
+    fn colliders_at(
+        &self,
+        this: Point,
+        except: ObjectId,
+    ) -> impl Iterator<Item = &Rc<RefCell<dyn Colliding<'r> + 'r>>> {
+        self.get_at(this)
+            .iter()
+            .filter(move |id| **id != except)
+            .filter_map(|id| self.colliders.get(id))
+    }
+    /// The effects of every tile at the point, for whoever stands there
+    pub fn get_tile_effects(&self, this: Point, except: ObjectId) -> Vec<Box<dyn Effect<'r> + 'r>> {
+        self.colliders_at(this, except)
+            .flat_map(|col| col.borrow().get_tile_effects())
+            .collect()
+    }
+
+    /// The team of whatever is standing on the tile, if it belongs to one
+    pub fn check_team(&self, this: Point, except: ObjectId) -> Option<u8> {
+        self.colliders_at(this, except)
+            .find_map(|col| col.borrow().get_team())
     }
 
-    // pub fn check_raycast(
-    //     &mut self,
-    //     this: &dyn Colliding,
-    //     start: &Vector2d,
-    //     end: Vector2d,
-    // ) -> Vector2d {
-    //     let mut ret = false;
-    //     let col = this.get_collider();
-    //     let _col = Rc::clone(&col);
+    pub fn check_tag(&self, this: Point, except: ObjectId) -> Option<BudTag> {
+        self.colliders_at(this, except)
+            .find_map(|col| col.borrow().get_tag())
+    }
 
-    //     let mut ret_end = end;
-    //     let mut min = None;
-    //     let mut min_distance_squared = i32::MAX;
+    /// The side an attack travelling along `direction` lands on, if something on the tile has a facing
+    pub fn check_flank(
+        &self,
+        this: Point,
+        direction: &Direction,
+        except: ObjectId,
+    ) -> Option<Flank> {
+        self.colliders_at(this, except).find_map(|col| {
+            let col = col.borrow();
+            col.get_facing()
+                .map(|facing| Flank::new(direction, &facing, col.blocks_front()))
+        })
+    }
 
-    //     for other in &self.colliders {
-    //         match other.try_borrow_mut() {
-    //             Ok(mut val) => {
-    //                 let other_col = val.get_collider();
-    //                 let _other_col = other_col.borrow();
+    /// Triggers everything on the tile for whoever just stepped onto it
+    pub fn enter_tile(&self, this: Point, except: ObjectId) -> Vec<Box<dyn Effect<'r> + 'r>> {
+        self.colliders_at(this, except)
+            .flat_map(|col| col.borrow_mut().on_enter())
+            .collect()
+    }
 
-    //                 if let Some(new_end) = Self::contains_raycast(start, &ret_end, &_other_col) {
-    //                     let new_distance_squared = ((new_end.x - start.x) as i32).pow(2)
-    //                         + ((new_end.y - start.y) as i32).pow(2);
-    //                     if new_distance_squared < min_distance_squared {
-    //                         min_distance_squared = new_distance_squared;
-    //                         min = Some(other);
-    //                         ret_end = new_end;
-    //                     }
-    //                 }
-    //                 // if let Some(side) = _col.borrow().contains(&_other_col) {
-    //                 //     val.has_collided(this, side.clone());
-    //                 //     this.has_collided(&mut *val, !side.clone());
-    //                 // }
-    //                 ret = true;
-    //             }
-    //             Err(_) => continue,
-    //         }
-    //     }
-    //     if let Some(min) = min {
-    //         min.borrow_mut().has_collided(this, Side::Top);
-    //     }
-    //     ret_end
-    //     // if ret {
-    //     //     Some(ret_end)
-    //     // } else {
-    //     //     None
-    //     // }
-    // }
-    // fn contains_raycast(start: &Vector2d, end: &Vector2d, other: &Collider) -> Option<Vector2d> {
-    //     let x1 = start.x;
-    //     let y1 = start.y;
-    //     let mut x2 = end.x;
-    //     let mut y2 = end.y;
-    //     let rx = other.a.borrow().x;
-    //     let ry = other.a.borrow().y;
-    //     let rw = other.b.x;
-    //     let rh = other.b.y;
+    /// Hands the effect to everything on the tile, so a bud standing on a tile object is hit too
+    pub fn impact_tile(
+        &mut self,
+        this: Point,
+        effect: Box<dyn Effect<'r> + 'r>,
+        except: ObjectId,
+    ) -> bool {
+        let mut hit = false;
+        for col in self.colliders_at(this, except) {
+            col.borrow_mut()
+                .on_effected(dyn_clone::clone_box(&*effect), self.get_colliders());
+            hit = true;
+        }
+        hit
+    }
 
-    //     let mut ret = None;
-    //     // let mut min_distance_squared = None;
-    //     // Lefts
-    //     if let Some(new_end) = Self::lineline(x1, y1, x2, y2, rx, ry, rx, ry + rh) {
-    //         x2 = new_end.x;
-    //         y2 = new_end.y;
-    //         ret = Some(Vector2d::new(x2, y2));
-    //     }
-    //     // Right
-    //     if let Some(new_end) = Self::lineline(x1, y1, x2, y2, rx + rw, ry, rx + rw, ry + rh) {
-    //         x2 = new_end.x;
-    //         y2 = new_end.y;
-    //         ret = Some(Vector2d::new(x2, y2));
-    //     }
-    //     // Top
-    //     if let Some(new_end) = Self::lineline(x1, y1, x2, y2, rx, ry, rx + rw, ry) {
-    //         x2 = new_end.x;
-    //         y2 = new_end.y;
-    //         ret = Some(Vector2d::new(x2, y2));
-    //     }
-    //     // Bottom
-    //     if let Some(new_end) = Self::lineline(x1, y1, x2, y2, rx, ry + rh, rx + rw, ry + rh) {
-    //         x2 = new_end.x;
-    //         y2 = new_end.y;
-    //         ret = Some(Vector2d::new(x2, y2));
-    //     }
-    //     ret
-    // }
+    /// Walks the grid from `start` towards `end` and returns the first occupied tile, not counting `start`
+    pub fn raycast(&self, start: Point, end: Point) -> Option<Point> {
+        Self::line(start, end)
+            .into_iter()
+            .skip(1)
+            .find(|tile| self.check_tile(*tile))
+    }
+    /// Every tile on the line from `start` to `end`, both included (Bresenham)
+    pub fn line(start: Point, end: Point) -> Vec<Point> {
+        let mut ret = Vec::new();
+        let dx = (end.x - start.x).abs();
+        let dy = -(end.y - start.y).abs();
+        let step_x = if start.x < end.x { 1 } else { -1 };
+        let step_y = if start.y < end.y { 1 } else { -1 };
+        let mut error = dx + dy;
+        let mut current = start;
+        loop {
+            ret.push(current);
+            if current == end {
+                break;
+            }
+            let error_2 = 2 * error;
+            if error_2 >= dy {
+                error += dy;
+                current.x += step_x;
+            }
+            if error_2 <= dx {
+                error += dx;
+                current.y += step_y;
+            }
+        }
+        ret
+    }
     fn lineline(
         x1: f32,
         y1: f32,
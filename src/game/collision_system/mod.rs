@@ -1 +1,2 @@
 pub mod collisions;
+pub mod pathfinding;
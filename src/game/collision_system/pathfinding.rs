@@ -0,0 +1,71 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use sdl2::rect::Point;
+
+use crate::game::collision_system::collisions::Collisions;
+
+/// Every tile a bud can walk to with the speed it has, following the same rules as moving
+pub struct Reach {
+    start: Point,
+    /// Cheapest cost to each tile and the tile it is reached from
+    costs: HashMap<Point, (u16, Point)>,
+}
+
+impl Reach {
+    pub fn new(start: Point, speed: u16, weight: u8, collisions: &Collisions) -> Self {
+        let mut costs = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        costs.insert(start, (0, start));
+        queue.push(Reverse((0, start.x, start.y)));
+        while let Some(Reverse((cost, x, y))) = queue.pop() {
+            let current = Point::new(x, y);
+            if costs.get(&current).is_some_and(|(best, _)| *best < cost) {
+                continue;
+            }
+            for step in [
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0),
+            ] {
+                let next = current + step;
+                if !collisions.can_enter(next, weight) {
+                    continue;
+                }
+                let next_cost = cost + collisions.get_terrain(next).move_cost();
+                if next_cost > speed || costs.get(&next).is_some_and(|(best, _)| *best <= next_cost)
+                {
+                    continue;
+                }
+                costs.insert(next, (next_cost, current));
+                queue.push(Reverse((next_cost, next.x, next.y)));
+            }
+        }
+        Self { start, costs }
+    }
+    pub fn get_cost(&self, tile: Point) -> Option<u16> {
+        self.costs.get(&tile).map(|(cost, _)| *cost)
+    }
+    pub fn get_tiles(&self) -> Vec<Point> {
+        self.costs
+            .keys()
+            .filter(|tile| **tile != self.start)
+            .copied()
+            .collect()
+    }
+    /// The steps from the start to `goal`, not counting the start
+    pub fn path_to(&self, goal: Point) -> Option<Vec<Point>> {
+        self.costs.get(&goal)?;
+        let mut path = Vec::new();
+        let mut current = goal;
+        while current != self.start {
+            path.push(current);
+            current = self.costs[&current].1;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
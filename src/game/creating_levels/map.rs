@@ -5,8 +5,12 @@ use std::collections::HashMap;
 use std::fs;
 use std::rc::Rc;
 
+use sdl2::rect::Point;
 use sdl2::render::Texture;
 
+use crate::game::creating_levels::terrain::Terrain;
+
+use crate::game::game_object::game_objects::pickup::PickupKind;
 use crate::game::game_object::game_objects::tiles::tile_object::TileObject;
 use crate::vector2d::Vector2d;
 
@@ -14,6 +18,8 @@ use crate::vector2d::Vector2d;
 pub struct Map {
     pub rules: HashMap<u32, RuleSet>,
     pub tiles: (Vec<u32>, (u32, u32)),
+    pub terrain: Vec<(Point, Terrain)>,
+    pub pickups: Vec<(Point, PickupKind)>,
 }
 
 impl<'t> Map {
@@ -21,6 +27,8 @@ impl<'t> Map {
         Map {
             rules: Map::generate_rules(rule_files),
             tiles: Map::generate_tiles(tile_map_file),
+            terrain: Map::generate_terrain(tile_map_file),
+            pickups: Map::generate_pickups(tile_map_file),
         }
     }
     pub fn make_tile_objects(&self, tex: Rc<RefCell<Texture<'t>>>) -> Vec<TileObject<'t>> {
@@ -60,6 +68,7 @@ impl<'t> Map {
                             Rc::clone(&tex),
                             true,
                             Vector2d::new(16.0 * (index % 8) as f32, 16.0 * (index / 8) as f32),
+                            &rule_set.behavior,
                         ));
                         break;
                     }
@@ -81,12 +90,34 @@ impl<'t> Map {
         let y = contents.len() as u32;
         for line in contents {
             for c in line.chars() {
-                vec.push(c.to_digit(10).unwrap());
+                // Terrain and pickup letters are floor as far as tiles go
+                vec.push(c.to_digit(10).unwrap_or(0));
             }
         }
         // println!("{:?}, ({}, {})", contents, x, y);
         (vec, (x, y))
     }
+    /// The terrain letters of the tile map, every other floor tile is plain
+    pub fn generate_terrain(tile_map_file: &str) -> Vec<(Point, Terrain)> {
+        Map::read_letters(tile_map_file, Terrain::from_char)
+    }
+    /// The pickups the tile map starts with
+    pub fn generate_pickups(tile_map_file: &str) -> Vec<(Point, PickupKind)> {
+        Map::read_letters(tile_map_file, PickupKind::from_char)
+    }
+    /// Every letter of the tile map that `from_char` knows, with where it is
+    fn read_letters<T>(tile_map_file: &str, from_char: fn(char) -> Option<T>) -> Vec<(Point, T)> {
+        let file_path = format!("assets/tile_map/tile_maps/{tile_map_file}");
+        let mut ret = Vec::new();
+        for (y, line) in fs::read_to_string(file_path).unwrap().lines().enumerate() {
+            for (x, c) in line.split_whitespace().flat_map(|s| s.chars()).enumerate() {
+                if let Some(kind) = from_char(c) {
+                    ret.push((Point::new(x as i32, y as i32), kind));
+                }
+            }
+        }
+        ret
+    }
     pub fn generate_rules(rule_files: Vec<&str>) -> HashMap<u32, RuleSet> {
         let mut rules = HashMap::new();
         for rule_file in rule_files {
@@ -108,7 +139,9 @@ impl<'t> Map {
             .collect();
         let mut count = 0;
         for line in contents {
-            if count == 0 {
+            if line.starts_with(|c: char| c.is_alphabetic()) {
+                rule_set.behavior.add_property(&line);
+            } else if count == 0 {
                 for c in line.chars().take(1) {
                     number = c.to_digit(10).unwrap();
                 }
@@ -144,10 +177,39 @@ impl<'t> Map {
 
 // type RuleSet = BTreeMap<Rule, u32>;
 //
+/// What a tile does besides blocking, declared in its rule set with lines like `hp 3` or `door`
+#[derive(Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct TileBehavior {
+    /// Damage it takes before breaking into floor
+    pub hp: Option<u16>,
+    /// Damage dealt to whoever starts their turn on it
+    pub spikes: Option<u16>,
+    /// Health restored to whoever starts their turn on it, which also clears harmful statuses
+    pub shrine: Option<u16>,
+    /// Blocks until it is hit, then stays open
+    pub door: bool,
+}
+
+impl TileBehavior {
+    /// Lines have their whitespace removed before this, so `hp 3` arrives as `hp3`
+    pub fn add_property(&mut self, line: &str) {
+        let name: String = line.chars().take_while(|c| c.is_alphabetic()).collect();
+        let value = line[name.len()..].parse().ok();
+        match name.as_str() {
+            "hp" => self.hp = value,
+            "spikes" => self.spikes = value,
+            "shrine" => self.shrine = value,
+            "door" => self.door = true,
+            _ => println!("Unknown tile property: {}", name),
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub struct RuleSet {
     rules: Vec<Rule>,
     types: Vec<u32>,
+    behavior: TileBehavior,
     // tile_map: u32,
 }
 impl RuleSet {
@@ -155,6 +217,7 @@ impl RuleSet {
         Self {
             rules: Vec::new(),
             types,
+            behavior: TileBehavior::default(),
             // tile_map,
         }
     }
@@ -162,6 +225,7 @@ impl RuleSet {
         Self {
             rules: Vec::new(),
             types: Vec::new(),
+            behavior: TileBehavior::default(),
             // tile_map,
         }
     }
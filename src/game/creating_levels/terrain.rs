@@ -0,0 +1,86 @@
+/// Floor a tile map can mark with a letter in place of an empty tile
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Terrain {
+    Plain,
+    Mud,
+    Water,
+    HighGround,
+    Cover,
+}
+
+/// Buds heavier than this sink in water
+const WATER_MAX_WEIGHT: u8 = 4;
+
+impl Terrain {
+    pub fn from_char(c: char) -> Option<Terrain> {
+        match c {
+            'm' => Some(Terrain::Mud),
+            'w' => Some(Terrain::Water),
+            'h' => Some(Terrain::HighGround),
+            'c' => Some(Terrain::Cover),
+            _ => None,
+        }
+    }
+    pub fn all() -> [Terrain; 4] {
+        [
+            Terrain::Mud,
+            Terrain::Water,
+            Terrain::HighGround,
+            Terrain::Cover,
+        ]
+    }
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Terrain::Plain => "Plain",
+            Terrain::Mud => "Mud",
+            Terrain::Water => "Water",
+            Terrain::HighGround => "High Ground",
+            Terrain::Cover => "Cover",
+        }
+    }
+    pub fn get_texture_name(&self) -> &'static str {
+        match self {
+            Terrain::Plain => "ground",
+            Terrain::Mud => "mud",
+            Terrain::Water => "water",
+            Terrain::HighGround => "high_ground",
+            Terrain::Cover => "cover",
+        }
+    }
+    pub fn get_description(&self) -> &'static str {
+        match self {
+            Terrain::Plain => "",
+            Terrain::Mud => "Costs 2 speed to enter.",
+            Terrain::Water => "Only buds of weight 4 or less can enter.",
+            Terrain::HighGround => "Ranged weapons shoot +1 tile further from here.",
+            Terrain::Cover => "Ranged attacks deal -1 damage to buds here.",
+        }
+    }
+    /// Speed it takes to step onto the tile
+    pub fn move_cost(&self) -> u16 {
+        match self {
+            Terrain::Mud => 2,
+            _ => 1,
+        }
+    }
+    pub fn passable(&self, weight: u8) -> bool {
+        match self {
+            Terrain::Water => weight <= WATER_MAX_WEIGHT,
+            _ => true,
+        }
+    }
+    /// Extra range for a ranged weapon fired from the tile
+    pub fn range_bonus(&self) -> u8 {
+        match self {
+            Terrain::HighGround => 1,
+            _ => 0,
+        }
+    }
+    /// Ranged damage taken by whoever stands on the tile
+    pub fn ranged_damage(&self, damage: u16) -> u16 {
+        match self {
+            Terrain::Cover => damage.saturating_sub(1),
+            _ => damage,
+        }
+    }
+}
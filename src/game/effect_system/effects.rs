@@ -4,23 +4,77 @@ use dyn_clone::DynClone;
 
 use crate::game::{
     collision_system::collisions::Colliding,
+    effect_system::statuses::{Duration, StackRule},
+    event_system::events::BudTag,
     game_object::game_objects::bud::{bud_data::BudData, Bud},
 };
 
 pub mod aura_effect;
+pub mod displace_effect;
+pub mod pickup_effect;
 pub mod self_effect;
+pub mod status_effect;
 
+/// Effects a tile hands to whoever starts their turn standing on it
 pub struct Tile<'g> {
-    effects: Vec<Rc<RefCell<dyn Effect<'g> + 'g>>>,
+    effects: Vec<Box<dyn Effect<'g> + 'g>>,
+}
+
+impl<'g> Tile<'g> {
+    pub fn new() -> Self {
+        Self {
+            effects: Vec::new(),
+        }
+    }
+    pub fn add_effect(&mut self, effect: Box<dyn Effect<'g> + 'g>) {
+        self.effects.push(effect);
+    }
+    pub fn get_effects(&self) -> Vec<Box<dyn Effect<'g> + 'g>> {
+        self.effects
+            .iter()
+            .map(|effect| dyn_clone::clone_box(&**effect))
+            .collect()
+    }
 }
 
 pub trait Effect<'g>: DynClone {
+    fn name(&self) -> &'static str {
+        "Effect"
+    }
+    fn duration(&self) -> Duration {
+        Duration::Instant
+    }
+    fn stack_rule(&self) -> StackRule {
+        StackRule::Stack
+    }
+    /// Added to the weight of a bud carrying this as a trait
+    fn weight(&self) -> i8 {
+        0
+    }
+    /// Points it takes from a team's budget when picked as a trait
+    fn cost(&self) -> u16 {
+        0
+    }
+    /// Damage dealt on landing, for things that only care about being hit
+    fn damage(&self) -> u16 {
+        0
+    }
+    /// Harmful statuses are the ones a cleanse removes
+    fn harmful(&self) -> bool {
+        false
+    }
     fn is_active(&self) -> bool;
     fn apply(
         &mut self,
         bud: Rc<RefCell<BudData<'g>>>,
         others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
     );
+    /// Called by a status once per stack for every tile the bud moves
+    fn on_move(&self, _bud: Rc<RefCell<BudData<'g>>>) {}
+    /// What a bud carrying this as a trait puts on whoever it hits
+    fn on_hit(&self, _source: BudTag) -> Option<Box<dyn Effect<'g> + 'g>> {
+        None
+    }
 }
 impl<'g> Clone for Box<dyn Effect<'g>> {
     fn clone(&self) -> Self {
@@ -0,0 +1,88 @@
+use std::{cell::RefCell, rc::Rc};
+
+use sdl2::rect::Point;
+
+use crate::game::{
+    collision_system::collisions::Colliding,
+    effect_system::effects::Effect,
+    event_system::events::BudTag,
+    game_object::game_objects::bud::{
+        bud_data::{BudData, Displacement},
+        Direction,
+    },
+};
+
+/// Moves the bud a number of tiles, pushed along the attack or pulled back towards the attacker
+#[derive(Clone)]
+pub struct DisplaceEffect {
+    applications: i32,
+    step: Point,
+    tiles: u8,
+    impact: bool,
+    weight: u8,
+    source: Option<BudTag>,
+}
+
+impl DisplaceEffect {
+    /// Pushes the bud along `direction`, hitting whatever it is pushed into
+    pub fn knockback(direction: &Direction, tiles: u8) -> Self {
+        Self {
+            applications: 1,
+            step: direction.get_point(),
+            tiles,
+            impact: true,
+            weight: 0,
+            source: None,
+        }
+    }
+    /// Pulls the bud against `direction`, stopping short of whatever is in the way
+    pub fn pull(direction: &Direction, tiles: u8) -> Self {
+        Self {
+            applications: 1,
+            step: direction.opposite().get_point(),
+            tiles,
+            impact: false,
+            weight: 0,
+            source: None,
+        }
+    }
+    /// Every point the attacker outweighs the bud moves it a tile further, up to two,
+    /// and every point less moves it a tile shorter
+    pub fn with_weight(mut self, weight: u8) -> Self {
+        self.weight = weight;
+        self
+    }
+    pub fn with_source(mut self, source: BudTag) -> Self {
+        self.source = Some(source);
+        self
+    }
+}
+
+impl<'g> Effect<'g> for DisplaceEffect {
+    fn name(&self) -> &'static str {
+        "Displace"
+    }
+
+    fn is_active(&self) -> bool {
+        self.applications > 0
+    }
+
+    fn apply(
+        &mut self,
+        bud: Rc<RefCell<BudData<'g>>>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+        self.applications -= 1;
+        let difference = self.weight as i16 - bud.borrow().weight() as i16;
+        let tiles = (self.tiles as i16 + difference.min(2)).max(0) as u8;
+        if tiles == 0 {
+            return;
+        }
+        bud.borrow_mut().displacement = Some(Displacement {
+            step: self.step,
+            tiles,
+            impact: self.impact,
+            source: self.source.clone(),
+        });
+    }
+}
@@ -0,0 +1,120 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::game::{
+    collision_system::collisions::Colliding,
+    effect_system::{
+        effects::Effect,
+        statuses::{Duration, StackRule},
+    },
+    event_system::events::CombatEvent,
+    game_object::game_objects::bud::{bud_data::BudData, weapon::WeaponInfo},
+};
+
+/// Only tells everyone the bud picked something up
+#[derive(Clone)]
+pub struct CollectEffect {
+    applications: i32,
+    name: &'static str,
+}
+
+impl CollectEffect {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            applications: 1,
+            name,
+        }
+    }
+}
+
+impl<'g> Effect<'g> for CollectEffect {
+    fn name(&self) -> &'static str {
+        "Collect"
+    }
+
+    fn is_active(&self) -> bool {
+        self.applications > 0
+    }
+
+    fn apply(
+        &mut self,
+        bud: Rc<RefCell<BudData<'g>>>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+        self.applications -= 1;
+        bud.borrow().emit(CombatEvent::Pickup {
+            bud: bud.borrow().tag(),
+            name: self.name,
+        });
+    }
+}
+
+/// Lends the bud a trait for a few turns, picking up the same one again starts it over
+#[derive(Clone)]
+pub struct PowerUpEffect<'r> {
+    effect: Box<dyn Effect<'r>>,
+    turns: u32,
+}
+
+impl<'g> PowerUpEffect<'g> {
+    pub fn new(effect: Box<dyn Effect<'g>>, turns: u32) -> Self {
+        Self { effect, turns }
+    }
+}
+
+impl<'g> Effect<'g> for PowerUpEffect<'g> {
+    fn name(&self) -> &'static str {
+        self.effect.name()
+    }
+    fn duration(&self) -> Duration {
+        Duration::Turns(self.turns)
+    }
+    fn stack_rule(&self) -> StackRule {
+        StackRule::Refresh
+    }
+    fn is_active(&self) -> bool {
+        self.effect.is_active()
+    }
+
+    fn apply(
+        &mut self,
+        bud: Rc<RefCell<BudData<'g>>>,
+        others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+        self.effect.apply(bud, others);
+    }
+}
+
+/// Trades the bud's weapon for the one in the crate until the round is over
+#[derive(Clone)]
+pub struct WeaponSwapEffect<'r> {
+    applications: i32,
+    weapon_info: WeaponInfo<'r>,
+}
+
+impl<'g> WeaponSwapEffect<'g> {
+    pub fn new(weapon_info: WeaponInfo<'g>) -> Self {
+        Self {
+            applications: 1,
+            weapon_info,
+        }
+    }
+}
+
+impl<'g> Effect<'g> for WeaponSwapEffect<'g> {
+    fn name(&self) -> &'static str {
+        "Weapon Swap"
+    }
+
+    fn is_active(&self) -> bool {
+        self.applications > 0
+    }
+
+    fn apply(
+        &mut self,
+        bud: Rc<RefCell<BudData<'g>>>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+        self.applications -= 1;
+        bud.borrow_mut().weapon_info = self.weapon_info.clone();
+    }
+}
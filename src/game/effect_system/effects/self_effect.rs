@@ -1,7 +1,15 @@
 use std::{cell::RefCell, rc::Rc};
 
 use crate::game::{
-    collision_system::collisions::Colliding, effect_system::effects::Effect,
+    collision_system::collisions::Colliding,
+    effect_system::{
+        effects::{
+            status_effect::{PoisonEffect, RootEffect},
+            Effect,
+        },
+        statuses::Duration,
+    },
+    event_system::events::{BudTag, CombatEvent},
     game_object::game_objects::bud::bud_data::BudData,
 };
 
@@ -15,6 +23,12 @@ impl SelfEffect {
 }
 
 impl<'g> Effect<'g> for SelfEffect {
+    fn name(&self) -> &'static str {
+        "Trait"
+    }
+    fn duration(&self) -> Duration {
+        Duration::Permanent
+    }
     fn is_active(&self) -> bool {
         // todo!()
         true
@@ -33,6 +47,7 @@ impl<'g> Effect<'g> for SelfEffect {
 pub struct DamageEffect {
     applications: i32,
     damage: u16,
+    source: Option<BudTag>,
 }
 
 #[derive(Clone)]
@@ -51,6 +66,15 @@ impl FighterEffect {
 }
 
 impl<'g> Effect<'g> for FighterEffect {
+    fn name(&self) -> &'static str {
+        "Fighter"
+    }
+    fn duration(&self) -> Duration {
+        Duration::Permanent
+    }
+    fn cost(&self) -> u16 {
+        3
+    }
     fn is_active(&self) -> bool {
         self.applications > 0
     }
@@ -61,6 +85,10 @@ impl<'g> Effect<'g> for FighterEffect {
         others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
     ) {
         self.applications -= 1;
+        bud.borrow().emit(CombatEvent::Trait {
+            bud: bud.borrow().tag(),
+            name: "Fighter",
+        });
         bud.borrow_mut().damage += self.damage;
     }
 }
@@ -80,6 +108,18 @@ impl BulwarkEffect {
 }
 
 impl<'g> Effect<'g> for BulwarkEffect {
+    fn name(&self) -> &'static str {
+        "Bulwark"
+    }
+    fn duration(&self) -> Duration {
+        Duration::Permanent
+    }
+    fn cost(&self) -> u16 {
+        2
+    }
+    fn weight(&self) -> i8 {
+        1
+    }
     fn is_active(&self) -> bool {
         self.applications > 0
     }
@@ -90,6 +130,10 @@ impl<'g> Effect<'g> for BulwarkEffect {
         others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
     ) {
         self.applications -= 1;
+        bud.borrow().emit(CombatEvent::Trait {
+            bud: bud.borrow().tag(),
+            name: "Bulwark",
+        });
         bud.borrow_mut().max_health += self.health;
     }
 }
@@ -110,6 +154,18 @@ impl ScoutEffect {
 }
 
 impl<'g> Effect<'g> for ScoutEffect {
+    fn name(&self) -> &'static str {
+        "Scout"
+    }
+    fn duration(&self) -> Duration {
+        Duration::Permanent
+    }
+    fn cost(&self) -> u16 {
+        2
+    }
+    fn weight(&self) -> i8 {
+        -1
+    }
     fn is_active(&self) -> bool {
         self.applications > 0
     }
@@ -120,6 +176,10 @@ impl<'g> Effect<'g> for ScoutEffect {
         others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
     ) {
         self.applications -= 1;
+        bud.borrow().emit(CombatEvent::Trait {
+            bud: bud.borrow().tag(),
+            name: "Scout",
+        });
         bud.borrow_mut().speed += self.speed;
     }
 }
@@ -140,6 +200,15 @@ impl MendingEffect {
 }
 
 impl<'g> Effect<'g> for MendingEffect {
+    fn name(&self) -> &'static str {
+        "Mending"
+    }
+    fn duration(&self) -> Duration {
+        Duration::Permanent
+    }
+    fn cost(&self) -> u16 {
+        3
+    }
     fn is_active(&self) -> bool {
         self.applications > 0
     }
@@ -149,9 +218,104 @@ impl<'g> Effect<'g> for MendingEffect {
         bud: Rc<RefCell<BudData<'g>>>,
         others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
     ) {
-        println!("how many apps");
         self.applications -= 1;
+        bud.borrow().emit(CombatEvent::Trait {
+            bud: bud.borrow().tag(),
+            name: "Mending",
+        });
         bud.borrow_mut().add_health(self.healing);
+        // Statuses already ticked for this turn, so a harmful one still lands once
+        CleanseEffect::new().apply(bud, others);
+    }
+}
+
+/// Poisons whoever the bud hits
+#[derive(Clone)]
+pub struct VenomousEffect {
+    applications: i32,
+    damage: u16,
+    turns: u32,
+}
+
+impl VenomousEffect {
+    pub fn new() -> Self {
+        Self {
+            applications: 1,
+            damage: 1,
+            turns: 2,
+        }
+    }
+}
+
+impl<'g> Effect<'g> for VenomousEffect {
+    fn name(&self) -> &'static str {
+        "Venomous"
+    }
+    fn duration(&self) -> Duration {
+        Duration::Permanent
+    }
+    fn cost(&self) -> u16 {
+        3
+    }
+    fn is_active(&self) -> bool {
+        self.applications > 0
+    }
+
+    fn apply(
+        &mut self,
+        _bud: Rc<RefCell<BudData<'g>>>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+        self.applications -= 1;
+    }
+
+    fn on_hit(&self, source: BudTag) -> Option<Box<dyn Effect<'g> + 'g>> {
+        Some(Box::new(
+            PoisonEffect::new(self.damage, self.turns).with_source(source),
+        ))
+    }
+}
+
+/// Roots whoever the bud hits for their next turn
+#[derive(Clone)]
+pub struct EntanglingEffect {
+    applications: i32,
+    turns: u32,
+}
+
+impl EntanglingEffect {
+    pub fn new() -> Self {
+        Self {
+            applications: 1,
+            turns: 1,
+        }
+    }
+}
+
+impl<'g> Effect<'g> for EntanglingEffect {
+    fn name(&self) -> &'static str {
+        "Entangling"
+    }
+    fn duration(&self) -> Duration {
+        Duration::Permanent
+    }
+    fn cost(&self) -> u16 {
+        3
+    }
+    fn is_active(&self) -> bool {
+        self.applications > 0
+    }
+
+    fn apply(
+        &mut self,
+        _bud: Rc<RefCell<BudData<'g>>>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+        self.applications -= 1;
+    }
+
+    fn on_hit(&self, _source: BudTag) -> Option<Box<dyn Effect<'g> + 'g>> {
+        Some(Box::new(RootEffect::new(self.turns)))
     }
 }
 
@@ -160,11 +324,22 @@ impl DamageEffect {
         Self {
             applications: 1,
             damage,
+            source: None,
         }
     }
+    pub fn with_source(mut self, source: BudTag) -> Self {
+        self.source = Some(source);
+        self
+    }
 }
 
 impl<'g> Effect<'g> for DamageEffect {
+    fn name(&self) -> &'static str {
+        "Damage"
+    }
+    fn damage(&self) -> u16 {
+        self.damage
+    }
     fn is_active(&self) -> bool {
         self.applications > 0
     }
@@ -175,6 +350,73 @@ impl<'g> Effect<'g> for DamageEffect {
         others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
     ) {
         self.applications -= 1;
-        bud.borrow_mut().remove_health(self.damage);
+        bud.borrow_mut()
+            .remove_health(self.damage, self.source.clone());
+    }
+}
+
+/// Restores health once when it lands
+#[derive(Clone)]
+pub struct HealEffect {
+    applications: i32,
+    healing: u16,
+}
+
+impl HealEffect {
+    pub fn new(healing: u16) -> Self {
+        Self {
+            applications: 1,
+            healing,
+        }
+    }
+}
+
+impl<'g> Effect<'g> for HealEffect {
+    fn name(&self) -> &'static str {
+        "Heal"
+    }
+
+    fn is_active(&self) -> bool {
+        self.applications > 0
+    }
+
+    fn apply(
+        &mut self,
+        bud: Rc<RefCell<BudData<'g>>>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+        self.applications -= 1;
+        bud.borrow_mut().add_health(self.healing);
+    }
+}
+
+/// Strips every harmful status off the bud
+#[derive(Clone)]
+pub struct CleanseEffect {
+    applications: i32,
+}
+
+impl CleanseEffect {
+    pub fn new() -> Self {
+        Self { applications: 1 }
+    }
+}
+
+impl<'g> Effect<'g> for CleanseEffect {
+    fn name(&self) -> &'static str {
+        "Cleanse"
+    }
+
+    fn is_active(&self) -> bool {
+        self.applications > 0
+    }
+
+    fn apply(
+        &mut self,
+        bud: Rc<RefCell<BudData<'g>>>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+        self.applications -= 1;
+        bud.borrow_mut().statuses.cleanse();
     }
 }
@@ -0,0 +1,191 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::game::{
+    collision_system::collisions::Colliding,
+    effect_system::{
+        effects::Effect,
+        statuses::{Duration, StackRule},
+    },
+    event_system::events::{BudTag, CombatEvent},
+    game_object::game_objects::bud::bud_data::BudData,
+};
+
+/// Deals its damage at the start of each turn
+#[derive(Clone)]
+pub struct PoisonEffect {
+    applications: i32,
+    damage: u16,
+    turns: u32,
+    source: Option<BudTag>,
+}
+
+impl PoisonEffect {
+    pub fn new(damage: u16, turns: u32) -> Self {
+        Self {
+            applications: 1,
+            damage,
+            turns,
+            source: None,
+        }
+    }
+    pub fn with_source(mut self, source: BudTag) -> Self {
+        self.source = Some(source);
+        self
+    }
+}
+
+impl<'g> Effect<'g> for PoisonEffect {
+    fn name(&self) -> &'static str {
+        "Poison"
+    }
+    fn duration(&self) -> Duration {
+        Duration::Turns(self.turns)
+    }
+    fn harmful(&self) -> bool {
+        true
+    }
+
+    fn is_active(&self) -> bool {
+        self.applications > 0
+    }
+
+    fn apply(
+        &mut self,
+        bud: Rc<RefCell<BudData<'g>>>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+        self.applications -= 1;
+        bud.borrow_mut()
+            .remove_health(self.damage, self.source.clone());
+    }
+}
+
+/// Deals its damage for every tile the bud moves
+#[derive(Clone)]
+pub struct BleedEffect {
+    damage: u16,
+    turns: u32,
+    source: Option<BudTag>,
+}
+
+impl BleedEffect {
+    pub fn new(damage: u16, turns: u32) -> Self {
+        Self {
+            damage,
+            turns,
+            source: None,
+        }
+    }
+    pub fn with_source(mut self, source: BudTag) -> Self {
+        self.source = Some(source);
+        self
+    }
+}
+
+impl<'g> Effect<'g> for BleedEffect {
+    fn name(&self) -> &'static str {
+        "Bleed"
+    }
+    fn duration(&self) -> Duration {
+        Duration::Turns(self.turns)
+    }
+    fn harmful(&self) -> bool {
+        true
+    }
+
+    fn is_active(&self) -> bool {
+        true
+    }
+
+    fn apply(
+        &mut self,
+        _bud: Rc<RefCell<BudData<'g>>>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+    }
+
+    fn on_move(&self, bud: Rc<RefCell<BudData<'g>>>) {
+        bud.borrow_mut()
+            .remove_health(self.damage, self.source.clone());
+    }
+}
+
+/// The bud skips its next turn, checked by the `TurnHandler` before the turn starts
+#[derive(Clone)]
+pub struct StunEffect {
+    turns: u32,
+}
+
+impl StunEffect {
+    pub fn new(turns: u32) -> Self {
+        Self { turns }
+    }
+}
+
+impl<'g> Effect<'g> for StunEffect {
+    fn name(&self) -> &'static str {
+        "Stun"
+    }
+    fn duration(&self) -> Duration {
+        Duration::Turns(self.turns)
+    }
+    fn stack_rule(&self) -> StackRule {
+        StackRule::Ignore
+    }
+    fn harmful(&self) -> bool {
+        true
+    }
+
+    fn is_active(&self) -> bool {
+        true
+    }
+
+    fn apply(
+        &mut self,
+        bud: Rc<RefCell<BudData<'g>>>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+        bud.borrow().emit(CombatEvent::Stunned {
+            bud: bud.borrow().tag(),
+        });
+    }
+}
+
+/// The bud can not move on its turn
+#[derive(Clone)]
+pub struct RootEffect {
+    turns: u32,
+}
+
+impl RootEffect {
+    pub fn new(turns: u32) -> Self {
+        Self { turns }
+    }
+}
+
+impl<'g> Effect<'g> for RootEffect {
+    fn name(&self) -> &'static str {
+        "Root"
+    }
+    fn duration(&self) -> Duration {
+        Duration::Turns(self.turns)
+    }
+    fn stack_rule(&self) -> StackRule {
+        StackRule::Refresh
+    }
+    fn harmful(&self) -> bool {
+        true
+    }
+
+    fn is_active(&self) -> bool {
+        true
+    }
+
+    fn apply(
+        &mut self,
+        bud: Rc<RefCell<BudData<'g>>>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+        bud.borrow_mut().speed = 0;
+    }
+}
@@ -0,0 +1,146 @@
+use crate::game::{effect_system::effects::Effect, event_system::events::BudTag};
+
+/// How long an effect stays on a bud once it lands
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Duration {
+    /// Applied once when it lands and never kept
+    Instant,
+    /// Applied at the start of each of the bud's next turns
+    Turns(u32),
+    /// Applied at the start of every turn until removed
+    Permanent,
+}
+
+/// What happens when an effect lands on a bud that already has it
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StackRule {
+    /// Adds a stack, each stack applies the effect once more
+    Stack,
+    /// Resets the remaining turns, a permanent status stays permanent
+    Refresh,
+    /// The new one is dropped
+    Ignore,
+}
+
+pub struct Status<'g> {
+    effect: Box<dyn Effect<'g> + 'g>,
+    remaining: Duration,
+    stacks: u8,
+}
+
+impl<'g> Status<'g> {
+    pub fn get_name(&self) -> &'static str {
+        self.effect.name()
+    }
+    pub fn get_remaining(&self) -> Duration {
+        self.remaining
+    }
+    pub fn get_stacks(&self) -> u8 {
+        self.stacks
+    }
+    pub fn is_harmful(&self) -> bool {
+        self.effect.harmful()
+    }
+}
+
+/// Every lasting effect on a bud, ticked once at the start of its turns
+pub struct StatusList<'g> {
+    statuses: Vec<Status<'g>>,
+}
+
+impl<'g> StatusList<'g> {
+    pub fn new() -> Self {
+        Self {
+            statuses: Vec::new(),
+        }
+    }
+    /// Instant effects are not kept and should be applied by the caller straight away
+    pub fn add(&mut self, effect: Box<dyn Effect<'g> + 'g>) {
+        let duration = effect.duration();
+        if duration == Duration::Instant {
+            return;
+        }
+        if let Some(status) = self
+            .statuses
+            .iter_mut()
+            .find(|status| status.get_name() == effect.name())
+        {
+            match effect.stack_rule() {
+                StackRule::Stack => {
+                    status.stacks = status.stacks.saturating_add(1);
+                    status.remaining = Self::longest(status.remaining, duration);
+                }
+                // A power-up lending a trait the bud already has mustn't put an end to it
+                StackRule::Refresh if status.remaining != Duration::Permanent => {
+                    status.remaining = duration
+                }
+                StackRule::Refresh => {}
+                StackRule::Ignore => {}
+            }
+            return;
+        }
+        self.statuses.push(Status {
+            effect,
+            remaining: duration,
+            stacks: 1,
+        });
+    }
+    /// Counts down every status and hands back the effects to apply this turn, one per stack
+    pub fn tick(&mut self) -> Vec<Box<dyn Effect<'g> + 'g>> {
+        let mut ret = Vec::new();
+        for status in self.statuses.iter_mut() {
+            for _ in 0..status.stacks {
+                ret.push(dyn_clone::clone_box(&*status.effect));
+            }
+            if let Duration::Turns(turns) = &mut status.remaining {
+                *turns = turns.saturating_sub(1);
+            }
+        }
+        self.statuses
+            .retain(|status| status.remaining != Duration::Turns(0));
+        ret
+    }
+    /// The effects to run for one tile of movement, one per stack
+    pub fn on_move(&self) -> Vec<Box<dyn Effect<'g> + 'g>> {
+        let mut ret = Vec::new();
+        for status in self.statuses.iter() {
+            for _ in 0..status.stacks {
+                ret.push(dyn_clone::clone_box(&*status.effect));
+            }
+        }
+        ret
+    }
+    /// The effects to land on a hit target, one per stack
+    pub fn on_hit(&self, source: BudTag) -> Vec<Box<dyn Effect<'g> + 'g>> {
+        let mut ret = Vec::new();
+        for status in self.statuses.iter() {
+            for _ in 0..status.stacks {
+                ret.extend(status.effect.on_hit(source.clone()));
+            }
+        }
+        ret
+    }
+    pub fn has(&self, name: &str) -> bool {
+        self.statuses.iter().any(|status| status.get_name() == name)
+    }
+    pub fn remove(&mut self, name: &str) {
+        self.statuses.retain(|status| status.get_name() != name);
+    }
+    /// Removes every harmful status
+    pub fn cleanse(&mut self) {
+        self.statuses.retain(|status| !status.is_harmful());
+    }
+    pub fn get_statuses(&self) -> &[Status<'g>] {
+        &self.statuses
+    }
+
+    fn longest(a: Duration, b: Duration) -> Duration {
+        match (a, b) {
+            (Duration::Permanent, _) | (_, Duration::Permanent) => Duration::Permanent,
+            (Duration::Turns(a), Duration::Turns(b)) => Duration::Turns(a.max(b)),
+            (Duration::Turns(turns), Duration::Instant)
+            | (Duration::Instant, Duration::Turns(turns)) => Duration::Turns(turns),
+            (Duration::Instant, Duration::Instant) => Duration::Instant,
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::game::event_system::events::{BudTag, CombatEvent, EventListener};
+
+#[derive(Debug)]
+pub struct LogEntry {
+    pub turn: u32,
+    /// Teams taking part, an empty list concerns everyone
+    pub teams: Vec<u8>,
+    pub text: String,
+}
+
+/// Every combat event worth reading, written out for the combat log panel. One log runs for the
+/// whole match, with a line between rounds
+#[derive(Debug)]
+pub struct CombatLog {
+    entries: Vec<LogEntry>,
+}
+
+impl CombatLog {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+    pub fn get_entries(&self, team: Option<u8>) -> Vec<&LogEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| match team {
+                Some(team) => entry.teams.is_empty() || entry.teams.contains(&team),
+                None => true,
+            })
+            .collect()
+    }
+    /// Marks where the round starts, turns count from zero again after it
+    pub fn start_round(&mut self, round: u32) {
+        self.push(0, &[], format!("Round {}", round + 1));
+    }
+    fn push(&mut self, turn: u32, buds: &[&BudTag], text: String) {
+        let mut teams: Vec<u8> = buds.iter().map(|bud| bud.team).collect();
+        teams.dedup();
+        self.entries.push(LogEntry { turn, teams, text });
+    }
+}
+
+impl EventListener for CombatLog {
+    fn on_event(&mut self, turn: u32, event: &CombatEvent) {
+        match event {
+            CombatEvent::TurnStart { bud } => {
+                self.push(turn, &[bud], format!("{}'s turn", bud.name));
+            }
+            CombatEvent::Attack { attacker, weapon } => {
+                self.push(
+                    turn,
+                    &[attacker],
+                    format!("{} attacks with {:?}", attacker.name, weapon),
+                );
+            }
+            CombatEvent::Damage {
+                target,
+                source: Some(source),
+                amount,
+            } => {
+                self.push(
+                    turn,
+                    &[source, target],
+                    format!("{} hit {} for {} dmg", source.name, target.name, amount),
+                );
+            }
+            CombatEvent::Damage {
+                target,
+                source: None,
+                amount,
+            } => {
+                self.push(
+                    turn,
+                    &[target],
+                    format!("{} took {} dmg", target.name, amount),
+                );
+            }
+            CombatEvent::Blocked { target, source } => {
+                self.push(
+                    turn,
+                    &[source, target],
+                    format!("{} blocked {}", target.name, source.name),
+                );
+            }
+            CombatEvent::Heal { target, amount } => {
+                self.push(
+                    turn,
+                    &[target],
+                    format!("{} healed {} health", target.name, amount),
+                );
+            }
+            CombatEvent::Trait { bud, name } => {
+                self.push(turn, &[bud], format!("{} triggered {}", bud.name, name));
+            }
+            CombatEvent::Stunned { bud } => {
+                self.push(turn, &[bud], format!("{} is stunned", bud.name));
+            }
+            CombatEvent::Placed { bud, name } => {
+                self.push(turn, &[bud], format!("{} placed {}", bud.name, name));
+            }
+            CombatEvent::Pickup { bud, name } => {
+                self.push(turn, &[bud], format!("{} picked up {}", bud.name, name));
+            }
+            CombatEvent::Death {
+                bud,
+                source: Some(source),
+            } => {
+                self.push(
+                    turn,
+                    &[source, bud],
+                    format!("{} was killed by {}", bud.name, source.name),
+                );
+            }
+            CombatEvent::Death { bud, source: None } => {
+                self.push(turn, &[bud], format!("{} died", bud.name));
+            }
+            CombatEvent::RoundEnd { winners } => {
+                let teams: Vec<String> =
+                    winners.iter().map(|team| (team + 1).to_string()).collect();
+                let text = match teams.len() {
+                    0 => String::from("Round over"),
+                    1 => format!("Round over, Team {} wins", teams[0]),
+                    _ => format!("Round over, Teams {} win", teams.join(" and ")),
+                };
+                self.push(turn, &[], text);
+            }
+            CombatEvent::TurnEnd { .. } | CombatEvent::Move { .. } => {}
+        }
+    }
+}
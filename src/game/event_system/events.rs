@@ -0,0 +1,110 @@
+use std::{cell::RefCell, rc::Rc};
+
+use sdl2::rect::Point;
+
+use crate::game::{game_object::game_objects::bud::weapon::WeaponEnum, registry::ObjectId};
+
+/// Who an event is about, without holding on to the bud itself
+#[derive(Clone, Debug)]
+pub struct BudTag {
+    pub id: ObjectId,
+    pub team: u8,
+    pub index: u8,
+    pub name: String,
+}
+
+#[derive(Clone, Debug)]
+pub enum CombatEvent {
+    TurnStart {
+        bud: BudTag,
+    },
+    TurnEnd {
+        bud: BudTag,
+    },
+    Attack {
+        attacker: BudTag,
+        weapon: WeaponEnum,
+    },
+    Damage {
+        target: BudTag,
+        source: Option<BudTag>,
+        amount: u16,
+    },
+    Blocked {
+        target: BudTag,
+        source: BudTag,
+    },
+    Heal {
+        target: BudTag,
+        amount: u16,
+    },
+    Trait {
+        bud: BudTag,
+        name: &'static str,
+    },
+    Stunned {
+        bud: BudTag,
+    },
+    Placed {
+        bud: BudTag,
+        name: &'static str,
+    },
+    Pickup {
+        bud: BudTag,
+        name: &'static str,
+    },
+    Death {
+        bud: BudTag,
+        source: Option<BudTag>,
+    },
+    Move {
+        bud: BudTag,
+        from: Point,
+        to: Point,
+    },
+    RoundEnd {
+        /// Every team on the side left standing, empty if nobody is
+        winners: Vec<u8>,
+    },
+}
+
+pub trait EventListener {
+    fn on_event(&mut self, turn: u32, event: &CombatEvent);
+}
+
+/// Queues combat events as they happen and hands them to every listener once per frame.
+/// Listeners should not emit events themselves while they are being dispatched to.
+pub struct EventBus<'g> {
+    turn: u32,
+    queue: Vec<(u32, CombatEvent)>,
+    listeners: Vec<Rc<RefCell<dyn EventListener + 'g>>>,
+}
+
+impl<'g> EventBus<'g> {
+    pub fn new() -> Self {
+        Self {
+            turn: 0,
+            queue: Vec::new(),
+            listeners: Vec::new(),
+        }
+    }
+    pub fn subscribe(&mut self, listener: Rc<RefCell<dyn EventListener + 'g>>) {
+        self.listeners.push(listener);
+    }
+    pub fn next_turn(&mut self) {
+        self.turn += 1;
+    }
+    pub fn get_turn(&self) -> u32 {
+        self.turn
+    }
+    pub fn emit(&mut self, event: CombatEvent) {
+        self.queue.push((self.turn, event));
+    }
+    pub fn dispatch(&mut self) {
+        for (turn, event) in self.queue.drain(0..) {
+            for listener in self.listeners.iter() {
+                listener.borrow_mut().on_event(turn, &event);
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::game::{
+    event_system::events::{CombatEvent, EventListener},
+    match_info::MatchInfo,
+    match_settings::MatchSettings,
+};
+
+/// Gold for the team that lands a kill
+const KILL_GOLD: u32 = 3;
+/// Gold for each bud still alive when the round ends
+const SURVIVE_GOLD: u32 = 1;
+/// Gold for every team on the side that wins the round
+const WIN_GOLD: u32 = 5;
+
+/// Adds up the kills each team makes during a round, paid out with the rest when it ends
+pub struct GoldLedger {
+    earned: Vec<u32>,
+    /// Tells allies from enemies, so only enemy kills pay
+    settings: MatchSettings,
+}
+
+impl GoldLedger {
+    pub fn new() -> Self {
+        Self {
+            earned: Vec::new(),
+            settings: MatchSettings::new(),
+        }
+    }
+    pub fn set_settings(&mut self, settings: MatchSettings) {
+        self.settings = settings;
+    }
+    pub fn get_earned(&self, team: u8) -> u32 {
+        self.earned.get(team as usize).copied().unwrap_or(0)
+    }
+    fn earn(&mut self, team: u8, amount: u32) {
+        if self.earned.len() <= team as usize {
+            self.earned.resize(team as usize + 1, 0);
+        }
+        self.earned[team as usize] += amount;
+    }
+    /// Gives every team what it earned, plus gold for its survivors and for winning
+    pub fn pay_out(&self, match_info: &mut MatchInfo, survivors: &[u32], winners: &[u8]) {
+        for (team, count) in survivors.iter().enumerate() {
+            let team = team as u8;
+            let mut gold = self.get_earned(team) + count * SURVIVE_GOLD;
+            if winners.contains(&team) {
+                gold += WIN_GOLD;
+            }
+            match_info.add_gold(team, gold);
+        }
+    }
+}
+
+impl EventListener for GoldLedger {
+    fn on_event(&mut self, _turn: u32, event: &CombatEvent) {
+        if let CombatEvent::Death {
+            bud,
+            source: Some(source),
+        } = event
+        {
+            if self.settings.is_enemy(source.team, bud.team) {
+                self.earn(source.team, KILL_GOLD);
+            }
+        }
+    }
+}
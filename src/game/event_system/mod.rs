@@ -0,0 +1,4 @@
+pub mod combat_log;
+pub mod events;
+pub mod gold_ledger;
+pub mod xp_ledger;
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use crate::game::{
+    event_system::events::{CombatEvent, EventListener},
+    match_settings::MatchSettings,
+    registry::ObjectId,
+};
+
+/// Experience for each point of damage dealt to an enemy
+const DAMAGE_XP: u32 = 1;
+/// Experience for finishing off an enemy
+const KILL_XP: u32 = 5;
+/// Experience for living through the round
+pub const SURVIVE_XP: u32 = 3;
+
+/// What a bud did to the enemy in a round
+#[derive(Clone, Copy, Default, Debug)]
+pub struct RoundStats {
+    pub damage: u32,
+    pub kills: u32,
+}
+
+impl RoundStats {
+    pub fn get_xp(&self) -> u32 {
+        self.damage * DAMAGE_XP + self.kills * KILL_XP
+    }
+}
+
+/// Keeps each bud's stats for the round, turned into experience when it ends
+pub struct XpLedger {
+    stats: HashMap<ObjectId, RoundStats>,
+    /// Tells allies from enemies, so friendly fire earns nothing
+    settings: MatchSettings,
+}
+
+impl XpLedger {
+    pub fn new() -> Self {
+        Self {
+            stats: HashMap::new(),
+            settings: MatchSettings::new(),
+        }
+    }
+    pub fn set_settings(&mut self, settings: MatchSettings) {
+        self.settings = settings;
+    }
+    pub fn get_stats(&self, id: ObjectId) -> RoundStats {
+        self.stats.get(&id).copied().unwrap_or_default()
+    }
+}
+
+impl EventListener for XpLedger {
+    fn on_event(&mut self, _turn: u32, event: &CombatEvent) {
+        match event {
+            CombatEvent::Damage {
+                target,
+                source: Some(source),
+                amount,
+            } if self.settings.is_enemy(source.team, target.team) => {
+                self.stats.entry(source.id).or_default().damage += *amount as u32;
+            }
+            CombatEvent::Death {
+                bud,
+                source: Some(source),
+            } if self.settings.is_enemy(source.team, bud.team) => {
+                self.stats.entry(source.id).or_default().kills += 1;
+            }
+            _ => {}
+        }
+    }
+}
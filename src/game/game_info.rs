@@ -17,7 +17,10 @@ use crate::game::game_state::{
     GameState,
 };
 
-use super::{camera::Camera, input::Input};
+use super::{
+    camera::Camera, campaign::CampaignRun, input::Input, match_info::MatchInfo,
+    match_settings::MatchSettings, scenario::ScenarioRun,
+};
 
 pub struct GameInfo<'g> {
     pub running: bool,
@@ -29,6 +32,12 @@ pub struct GameInfo<'g> {
     pub sound_effects: HashMap<String, Rc<RefCell<Chunk>>>,
     pub restart: bool,
     pub game_state_handler: GameStateHandler<'g>,
+    pub match_settings: MatchSettings,
+    pub match_info: MatchInfo,
+    /// The campaign battle being played, if any
+    pub campaign: Option<CampaignRun>,
+    /// The scenario being played, if any
+    pub scenario: Option<ScenarioRun>,
 }
 
 impl<'g> GameInfo<'g> {
@@ -61,6 +70,10 @@ impl<'g> GameInfo<'g> {
             ),
             restart: false,
             game_state_handler: GameStateHandler::new(),
+            match_settings: MatchSettings::new(),
+            match_info: MatchInfo::new(),
+            campaign: None,
+            scenario: None,
         }
     }
 }
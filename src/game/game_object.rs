@@ -105,6 +105,14 @@ pub trait GameObject<'g> {
     ) -> bool {
         true
     }
+    /// Objects with higher initiative take their turns earlier in the round
+    fn initiative(&self) -> i32 {
+        0
+    }
+    /// Checked before `start`, an object that skips keeps its place in the turn order
+    fn skip_turn(&mut self, _collisions: &mut Collisions<'g>) -> bool {
+        false
+    }
     fn update(
         &mut self,
         _delta_time: f32,
@@ -1,5 +1,9 @@
 pub mod bud;
 pub mod ground;
+pub mod pickup;
+pub mod placeable;
+pub mod popup;
+pub mod terrain_layer;
 pub mod tiles;
 
 pub enum GameObjectEnum {
@@ -10,6 +10,7 @@ use sdl2::{
     pixels::Color,
     rect::{Point, Rect},
     render::{Canvas, Texture},
+    sys::SDL_SetTextureColorMod,
     video::Window,
     EventPump,
 };
@@ -18,56 +19,102 @@ use crate::{
     game::{
         button::Button,
         camera::Camera,
-        collision_system::collisions::{Colliding, Collisions, Side},
-        effect_system::effects::{aura_effect::AuraEffect, self_effect::DamageEffect, Effect},
+        collision_system::{
+            collisions::{Colliding, Collisions, Side},
+            pathfinding::Reach,
+        },
+        effect_system::{
+            effects::{aura_effect::AuraEffect, self_effect::DamageEffect, Effect},
+            statuses::Duration,
+        },
+        event_system::events::{BudTag, CombatEvent, EventBus},
         game_info::GameInfo,
         game_object::{
             game_objects::{
-                bud::bud_data::{BudData, InitialBudData},
+                bud::{
+                    ai::{Difficulty, Plan},
+                    attack_preview::{AttackPreview, Attacker},
+                    bud_data::{BudData, InitialBudData},
+                    weapon::WeaponEnum,
+                },
+                placeable::Placeable,
                 GameObjectEnum,
             },
             GameObject, SuperGameObject,
         },
         game_state::{game_states::select_state::NameGenerator, StateInfo},
         input::Input,
+        match_settings::MatchSettings,
         menu::menu_state::menu_states::{BudEnum, MenuStateEnum, MenuStateHandler},
+        registry::ObjectId,
     },
     vector2d::Vector2d,
 };
 
+pub mod ai;
+pub mod attack_preview;
 pub mod bud_data;
 pub mod weapon;
 
+/// Damage taken by both sides when a bud is knocked into something
+const IMPACT_DAMAGE: u16 = 1;
+/// Milliseconds between the steps of a computer-controlled bud, so its moves can be followed
+const COMPUTER_STEP_TIME: f32 = 300.0;
+
 pub struct Bud<'g> {
+    id: ObjectId,
     position: Point,
     pub bud_data: Rc<RefCell<BudData<'g>>>,
     hovered: bool,
     pressed: bool,
-    effects: Vec<Box<dyn Effect<'g> + 'g>>,
     moved: [bool; 4],
+    placed: bool,
     direction: Direction,
     active: bool,
     dead: bool,
+    attack_preview: AttackPreview,
+    reach: Vec<Point>,
+    /// The turn of a computer-controlled bud, made when its turn starts
+    plan: Option<Plan>,
+    computer_timer: f32,
 }
 impl<'g> Bud<'g> {
-    pub fn new(position: Point, initial_blud_data: InitialBudData<'g>) -> Self {
+    pub fn new(
+        id: ObjectId,
+        position: Point,
+        initial_blud_data: InitialBudData<'g>,
+        events: Rc<RefCell<EventBus<'g>>>,
+    ) -> Self {
         Self {
+            id,
             position,
-            bud_data: Rc::new(RefCell::new(BudData::default(initial_blud_data))),
+            bud_data: Rc::new(RefCell::new(BudData::default(
+                initial_blud_data,
+                id,
+                events,
+            ))),
             hovered: false,
             pressed: false,
-            effects: vec![],
             moved: [false, false, false, false],
+            placed: false,
             active: false,
             direction: Direction::Down,
             dead: false,
+            attack_preview: AttackPreview::new(),
+            reach: Vec::new(),
+            plan: None,
+            computer_timer: 0.0,
         }
     }
+    /// Turns the bud to face `direction`, as scenarios set buds up
+    pub fn face(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
 
     pub fn decide_move(
         &mut self,
         gi: &mut GameInfo<'g>,
-        collisions: &mut Collisions,
+        collisions: &mut Collisions<'g>,
         delta_time: f32,
     ) {
         if self.bud_data.borrow().speed > 0 {
@@ -107,45 +154,259 @@ impl<'g> Bud<'g> {
             self.move_bud(moving, collisions, delta_time);
         }
     }
-    fn died(&mut self) {
-        self.dead = true;
+    /// Plays the bud's turn for the computer one step at a time, then asks for the turn to end
+    fn play_computer_turn(
+        &mut self,
+        difficulty: Difficulty,
+        delta_time: f32,
+        collisions: &mut Collisions<'g>,
+        gi: &mut GameInfo<'g>,
+        si: &mut StateInfo<'g>,
+    ) {
+        self.computer_timer += delta_time;
+        if self.computer_timer < COMPUTER_STEP_TIME {
+            return;
+        }
+        self.computer_timer = 0.0;
+        let Some(plan) = &mut self.plan else {
+            let bud_data = Rc::clone(&self.bud_data);
+            self.plan = Some(ai::plan(
+                self.position,
+                &bud_data.borrow(),
+                collisions,
+                &gi.match_settings,
+                difficulty,
+            ));
+            return;
+        };
+        if plan.done {
+            return;
+        }
+        if plan.path.is_empty() {
+            self.direction = plan.direction;
+            plan.done = true;
+            si.end_turn = true;
+            return;
+        }
+        let step = plan.path.remove(0) - self.position;
+        self.direction = match (step.x, step.y) {
+            (0, -1) => Direction::Up,
+            (1, 0) => Direction::Right,
+            (-1, 0) => Direction::Left,
+            _ => Direction::Down,
+        };
+        self.move_bud(step, collisions, delta_time);
     }
-    fn attack(&mut self, collisions: &mut Collisions) {
-        let attack_tile = self.position + self.direction.get_point();
-        if (collisions.impact_tile(
-            attack_tile,
-            Box::new(DamageEffect::new(self.bud_data.borrow().damage)),
-        )) {}
+    /// Hands the bud to the registry once its health runs out, so it is removed from every list
+    /// A bud dying on its own turn ends it, as nothing else would for a computer-controlled bud
+    fn check_died(&mut self, si: &mut StateInfo<'g>) -> bool {
+        if !self.dead && self.bud_data.borrow().health == 0 {
+            self.dead = true;
+            si.registry.remove(self.id);
+            if self.active {
+                self.active = false;
+                si.end_turn = true;
+            }
+        }
+        self.dead
+    }
+    fn attack(&mut self, collisions: &mut Collisions<'g>) {
+        let bonus_damage = self.bud_data.borrow().damage;
+        let weight = self.bud_data.borrow().weight();
+        let weapon = self.bud_data.borrow().weapon_info.weapon.clone();
+        let aim = weapon.aim(self.position, &self.direction, collisions);
+        let attack_tiles = weapon.get_attack_tiles(aim.origin, &self.direction);
+        let tag = self.bud_data.borrow().tag();
+        self.bud_data.borrow().emit(CombatEvent::Attack {
+            attacker: tag.clone(),
+            weapon: weapon.weapon_enum.clone(),
+        });
+        for (attack_tile, damage) in attack_tiles {
+            let flank = collisions
+                .check_flank(attack_tile, &self.direction, self.id)
+                .unwrap_or(Flank::Front);
+            if flank == Flank::Blocked {
+                if let Some(target) = collisions.check_tag(attack_tile, self.id) {
+                    self.bud_data.borrow().emit(CombatEvent::Blocked {
+                        target,
+                        source: tag.clone(),
+                    });
+                }
+                continue;
+            }
+            let mut damage = flank.apply(damage as u16 + bonus_damage);
+            if weapon.is_ranged() {
+                damage = collisions.get_terrain(attack_tile).ranged_damage(damage);
+            }
+            collisions.impact_tile(
+                attack_tile,
+                Box::new(DamageEffect::new(damage).with_source(tag.clone())),
+                self.id,
+            );
+            if let Some(effect) = weapon.on_hit(tag.clone(), &self.direction, weight) {
+                collisions.impact_tile(attack_tile, effect, self.id);
+            }
+            let trait_effects = self.bud_data.borrow().statuses.on_hit(tag.clone());
+            for effect in trait_effects {
+                collisions.impact_tile(attack_tile, effect, self.id);
+            }
+        }
+    }
+    fn update_attack_preview(&mut self, collisions: &mut Collisions, settings: &MatchSettings) {
+        let bud_data = self.bud_data.borrow();
+        let aim = bud_data
+            .weapon_info
+            .weapon
+            .aim(self.position, &self.direction, collisions);
+        let attacker = Attacker {
+            id: self.id,
+            weapon: &bud_data.weapon_info.weapon,
+            direction: &self.direction,
+            bonus_damage: bud_data.damage,
+            team: bud_data.initial.team,
+        };
+        self.attack_preview
+            .update(aim, attacker, collisions, settings);
     }
 
-    pub fn move_bud(&mut self, moving: Point, collisions: &mut Collisions, delta_time: f32) {
-        if collisions.check_tile(self.position + moving) {
+    pub fn move_bud(&mut self, moving: Point, collisions: &mut Collisions<'g>, _delta_time: f32) {
+        let to = self.position + moving;
+        let weight = self.bud_data.borrow().weight();
+        let cost = collisions.get_terrain(to).move_cost();
+        if !collisions.can_enter(to, weight) || self.bud_data.borrow().speed < cost {
             return;
         }
         if moving.x != 0 || moving.y != 0 {
-            self.bud_data.borrow_mut().speed -= 1;
+            self.bud_data.borrow_mut().speed -= cost;
+            let from = self.position;
             self.position += moving;
+            collisions.move_collider(self.id, self.position);
+            self.bud_data.borrow().emit(CombatEvent::Move {
+                bud: self.bud_data.borrow().tag(),
+                from,
+                to: self.position,
+            });
+            let effects = self.bud_data.borrow().statuses.on_move();
+            for effect in effects {
+                effect.on_move(Rc::clone(&self.bud_data));
+            }
+            self.enter_tile(collisions);
+        }
+    }
+    /// Springs whatever is on the tile the bud just moved onto
+    fn enter_tile(&mut self, collisions: &mut Collisions<'g>) {
+        for effect in collisions.enter_tile(self.position, self.id) {
+            // A root takes hold straight away and ends the move that sprang it
+            let roots = effect.name() == "Root";
+            self.add_effect(effect, collisions.get_colliders());
+            if roots {
+                self.bud_data.borrow_mut().speed = 0;
+            }
+        }
+    }
+    /// Puts the bud's placeable on the tile in front of it, if the tile is empty
+    fn place(&mut self, collisions: &mut Collisions, si: &mut StateInfo<'g>) {
+        let Some(kind) = self.bud_data.borrow().weapon_info.weapon.get_placeable() else {
+            return;
+        };
+        let tile = self.position + self.direction.get_point();
+        if self.bud_data.borrow().placeables == 0 || !collisions.get_at(tile).is_empty() {
+            return;
+        }
+        self.bud_data.borrow_mut().placeables -= 1;
+        let tag = self.bud_data.borrow().tag();
+        let turn = si.events.borrow().get_turn();
+        si.add_colliding_object(Rc::new(RefCell::new(Placeable::new(
+            tile,
+            kind,
+            turn,
+            tag.clone(),
+        ))));
+        self.bud_data.borrow().emit(CombatEvent::Placed {
+            bud: tag,
+            name: kind.get_name(),
+        });
+    }
+    /// Carries out a push or pull one tile at a time, stopping at the first occupied tile
+    fn displace(&mut self, collisions: &mut Collisions<'g>) {
+        let Some(displacement) = self.bud_data.borrow_mut().displacement.take() else {
+            return;
+        };
+        let from = self.position;
+        let weight = self.bud_data.borrow().weight();
+        for _ in 0..displacement.tiles {
+            let next = self.position + displacement.step;
+            // Stops short of terrain the bud couldn't walk onto either, so it never lands in it
+            if !collisions.can_enter(next, weight) {
+                if displacement.impact {
+                    let tag = self.bud_data.borrow().tag();
+                    collisions.impact_tile(
+                        next,
+                        Box::new(DamageEffect::new(IMPACT_DAMAGE).with_source(tag)),
+                        self.id,
+                    );
+                    self.bud_data
+                        .borrow_mut()
+                        .remove_health(IMPACT_DAMAGE, displacement.source.clone());
+                }
+                break;
+            }
+            self.position = next;
+        }
+        if self.position != from {
+            collisions.move_collider(self.id, self.position);
+            self.bud_data.borrow().emit(CombatEvent::Move {
+                bud: self.bud_data.borrow().tag(),
+                from,
+                to: self.position,
+            });
+            self.enter_tile(collisions);
         }
     }
+    /// Instant effects apply right away, anything lasting becomes a status
     pub fn add_effect(
         &mut self,
         mut eff: Box<dyn Effect<'g> + 'g>,
         others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
     ) {
-        println!("EFFECTED");
-        eff.apply(Rc::clone(&self.bud_data), others);
-        self.effects.push(eff);
+        if eff.duration() == Duration::Instant {
+            eff.apply(Rc::clone(&self.bud_data), others);
+        } else {
+            self.bud_data.borrow_mut().statuses.add(eff);
+        }
     }
     pub fn apply_effects(&mut self, others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>) {
-        for i in 0..3 {
-            if let Some(new_effect) = self.bud_data.borrow_mut().initial.effects[i].clone() {
-                self.effects.push(new_effect);
-            }
-        }
-        self.effects
-            .iter_mut()
+        let effects = self.bud_data.borrow_mut().statuses.tick();
+        effects
+            .into_iter()
             .filter(|eff| eff.is_active())
-            .for_each(|eff| eff.apply(Rc::clone(&self.bud_data), others.clone()));
+            .for_each(|mut eff| eff.apply(Rc::clone(&self.bud_data), others.clone()));
+    }
+    /// A small marker per status above the sprite, red for harmful ones
+    fn draw_statuses(&self, canvas: &mut Canvas<Window>, camera: &mut Camera, sprite: Rect) {
+        let bud_data = self.bud_data.borrow();
+        let size = (camera.window_scale() * 4.0) as u32;
+        for (i, status) in bud_data.statuses.get_statuses().iter().enumerate() {
+            let rect = Rect::new(
+                sprite.x + (size as i32 + 1) * i as i32,
+                sprite.y - size as i32 - 1,
+                size,
+                size,
+            );
+            let color = if status.is_harmful() {
+                Color::RGB(200, 40, 40)
+            } else {
+                Color::RGB(40, 160, 60)
+            };
+            canvas.set_draw_color(color);
+            let _ = canvas.fill_rect(rect);
+            let _ = canvas.character(
+                rect.center().x as i16 - 4,
+                rect.center().y as i16 - 4,
+                status.get_name().chars().next().unwrap_or(' '),
+                Color::RGB(255, 255, 255),
+            );
+        }
     }
 }
 
@@ -165,6 +426,23 @@ impl<'g> GameObject<'g> for Bud<'g> {
         if self.bud_data.borrow().health == 0 {
             return;
         }
+        for tile in self.reach.iter() {
+            let mut rect = Rect::from_center(*tile, 16, 16);
+            camera.rect_to_camera(&mut rect);
+            canvas.set_draw_color(Color::RGBA(80, 160, 255, 50));
+            let _ = canvas.fill_rect(rect);
+        }
+        self.attack_preview.draw(canvas, camera);
+        // A disc at the feet in the team color
+        let mut tile_rect = Rect::from_center(self.position, 16, 16);
+        camera.rect_to_camera(&mut tile_rect);
+        let _ = canvas.filled_ellipse(
+            tile_rect.center().x as i16,
+            (tile_rect.bottom() - tile_rect.height() as i32 / 6) as i16,
+            tile_rect.width() as i16 / 3,
+            tile_rect.height() as i16 / 8,
+            MatchSettings::get_team_color(self.bud_data.borrow().initial.team),
+        );
         // let (position, size) = self.get_draw_values();
         canvas.set_draw_color(Color::RGBA(139, 210, 241, 255));
         let mut some_rect = Rect::from_center(self.position, 16, 21);
@@ -179,8 +457,14 @@ impl<'g> GameObject<'g> for Bud<'g> {
             Direction::Left => Rect::new(16, 21, 16, 21),
         };
 
+        let bud_data = self.bud_data.borrow();
+        // The texture is shared between buds, so the tint is set and reset around this one copy
+        let flash = bud_data.hit_flash > 0.0;
+        if flash {
+            unsafe { SDL_SetTextureColorMod(bud_data.initial.texture.raw(), 255, 60, 60) };
+        }
         canvas.copy_ex(
-            &self.bud_data.borrow().initial.texture,
+            &bud_data.initial.texture,
             tex_src,
             some_rect,
             0.0,
@@ -190,6 +474,10 @@ impl<'g> GameObject<'g> for Bud<'g> {
             false,
             false,
         );
+        if flash {
+            unsafe { SDL_SetTextureColorMod(bud_data.initial.texture.raw(), 255, 255, 255) };
+        }
+        self.draw_statuses(canvas, camera, some_rect);
     }
     fn start(
         &mut self,
@@ -199,58 +487,105 @@ impl<'g> GameObject<'g> for Bud<'g> {
         si: &mut StateInfo<'g>,
         msh: &mut MenuStateHandler<'g>,
     ) -> bool {
-        if !self.dead && self.bud_data.borrow().health == 0 {
-            self.dead = true;
-            collisions.remove(self.position);
-        }
-        if self.dead {
+        if self.check_died(si) {
             return false;
         }
         self.active = true;
-        self.apply_effects(collisions.colliders.clone());
+        self.apply_effects(collisions.get_colliders());
+        for effect in collisions.get_tile_effects(self.position, self.id) {
+            self.add_effect(effect, collisions.get_colliders());
+        }
 
-        println!(
-            "Start Turn! This is bud {}, with {} health.",
-            self.bud_data.borrow().initial.name,
-            self.bud_data.borrow().health
-        );
+        self.bud_data.borrow().emit(CombatEvent::TurnStart {
+            bud: self.bud_data.borrow().tag(),
+        });
+        self.plan = None;
+        self.computer_timer = 0.0;
+        si.computer_turn = gi
+            .match_settings
+            .get_computer(self.bud_data.borrow().initial.team)
+            .is_some();
 
-        msh.load_menu(MenuStateEnum::Bud(BudEnum::LeftBud(Some(Rc::clone(
-            &self.bud_data,
-        )))));
+        // An open combat log stays open from turn to turn
+        msh.load_menu_behind(
+            gi,
+            _delta_time,
+            MenuStateEnum::Bud(BudEnum::LeftBud(Some(Rc::clone(&self.bud_data)))),
+            &MenuStateEnum::CombatLog,
+        );
         true
     }
     fn end(
         &mut self,
         _delta_time: f32,
-        collisions: &mut Collisions,
+        collisions: &mut Collisions<'g>,
         gi: &mut GameInfo<'g>,
         si: &mut StateInfo<'g>,
         msh: &mut MenuStateHandler<'g>,
     ) -> bool {
-        if !self.dead && self.bud_data.borrow().health == 0 {
-            self.dead = true;
-            collisions.remove(self.position);
-        }
-        if self.dead {
+        if self.check_died(si) {
             return false;
         }
         self.active = false;
         self.attack(collisions);
-        println!("End Turn!");
+        self.bud_data.borrow().emit(CombatEvent::TurnEnd {
+            bud: self.bud_data.borrow().tag(),
+        });
         self.bud_data.borrow_mut().reset();
         return true;
     }
+    /// Lighter buds go first
+    fn initiative(&self) -> i32 {
+        -(self.bud_data.borrow().weight() as i32)
+    }
+    /// A stunned bud still ticks its statuses, which is also what wears the stun off
+    fn skip_turn(&mut self, collisions: &mut Collisions<'g>) -> bool {
+        if self.dead || !self.bud_data.borrow().statuses.has("Stun") {
+            return false;
+        }
+        self.apply_effects(collisions.get_colliders());
+        true
+    }
     fn update(
         &mut self,
         _delta_time: f32,
-        collisions: &mut Collisions,
+        collisions: &mut Collisions<'g>,
         gi: &mut GameInfo<'g>,
         si: &mut StateInfo<'g>,
         // level_info: &mut LevelInfo<'g>,
     ) -> bool {
-        if self.active {
+        if self.check_died(si) {
+            return false;
+        }
+        {
+            let mut bud_data = self.bud_data.borrow_mut();
+            bud_data.hit_flash = (bud_data.hit_flash - _delta_time).max(0.0);
+        }
+        self.displace(collisions);
+        let computer = gi
+            .match_settings
+            .get_computer(self.bud_data.borrow().initial.team);
+        if let (true, Some(difficulty)) = (self.active, computer) {
+            self.play_computer_turn(difficulty, _delta_time, collisions, gi, si);
+            self.reach.clear();
+        } else if self.active {
+            if gi.input.is_pressed(Keycode::P) && !self.placed {
+                self.placed = true;
+                self.place(collisions, si);
+            } else if gi.input.is_released(Keycode::P) {
+                self.placed = false;
+            }
             self.decide_move(gi, collisions, _delta_time);
+            let bud_data = self.bud_data.borrow();
+            self.reach = Reach::new(self.position, bud_data.speed, bud_data.weight(), collisions)
+                .get_tiles();
+        } else {
+            self.reach.clear();
+        }
+        if self.active || self.hovered {
+            self.update_attack_preview(collisions, &gi.match_settings);
+        } else {
+            self.attack_preview.clear();
         }
         true
     }
@@ -316,8 +651,28 @@ impl<'g> Colliding<'g> for Bud<'g> {
     fn get_collider(&self) -> Point {
         self.position
     }
+
+    fn get_team(&self) -> Option<u8> {
+        Some(self.bud_data.borrow().initial.team)
+    }
+
+    fn get_tag(&self) -> Option<BudTag> {
+        Some(self.bud_data.borrow().tag())
+    }
+
+    fn get_facing(&self) -> Option<Direction> {
+        Some(self.direction)
+    }
+
+    fn blocks_front(&self) -> bool {
+        matches!(
+            self.bud_data.borrow().weapon_info.weapon.weapon_enum,
+            WeaponEnum::Shield
+        )
+    }
 }
 
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Direction {
     Up,
     Right,
@@ -326,7 +681,7 @@ pub enum Direction {
 }
 
 impl Direction {
-    fn get_point(&self) -> Point {
+    pub fn get_point(&self) -> Point {
         match self {
             Direction::Up => Point::new(0, -1),
             Direction::Right => Point::new(1, 0),
@@ -334,4 +689,56 @@ impl Direction {
             Direction::Down => Point::new(0, 1),
         }
     }
+    /// Rotates a point given for a bud facing down to this direction
+    pub fn rotate(&self, point: Point) -> Point {
+        match self {
+            Direction::Up => Point::new(-point.x, -point.y),
+            Direction::Right => Point::new(point.y, -point.x),
+            Direction::Left => Point::new(-point.y, point.x),
+            Direction::Down => point,
+        }
+    }
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Left => Direction::Right,
+            Direction::Down => Direction::Up,
+        }
+    }
+}
+
+/// Which side of the defender an attack lands on
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Flank {
+    Front,
+    Side,
+    Back,
+    /// A frontal attack stopped by a shield
+    Blocked,
+}
+
+impl Flank {
+    /// `attack` is the direction the attack travels in
+    pub fn new(attack: &Direction, facing: &Direction, blocks_front: bool) -> Flank {
+        if attack == facing {
+            Flank::Back
+        } else if attack.opposite() == *facing {
+            if blocks_front {
+                Flank::Blocked
+            } else {
+                Flank::Front
+            }
+        } else {
+            Flank::Side
+        }
+    }
+    pub fn apply(&self, damage: u16) -> u16 {
+        match self {
+            Flank::Front => damage,
+            Flank::Side => damage + 1,
+            Flank::Back => damage + 2,
+            Flank::Blocked => 0,
+        }
+    }
 }
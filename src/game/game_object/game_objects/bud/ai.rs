@@ -0,0 +1,132 @@
+use rand::Rng;
+use sdl2::rect::Point;
+
+use crate::game::{
+    collision_system::{collisions::Collisions, pathfinding::Reach},
+    game_object::game_objects::bud::{bud_data::BudData, Direction, Flank},
+    match_settings::MatchSettings,
+};
+
+/// How well a computer-controlled team plays
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        match name {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+    /// Random score added to every option, so easier buds miss the best move more often
+    fn get_noise(&self) -> i32 {
+        match self {
+            Difficulty::Easy => 40,
+            Difficulty::Normal => 12,
+            Difficulty::Hard => 0,
+        }
+    }
+    /// Whether the bud cares which side it hits its target from
+    fn uses_flanks(&self) -> bool {
+        *self == Difficulty::Hard
+    }
+}
+
+/// Worth of a point of damage against closing a tile of distance to the nearest enemy
+const DAMAGE_SCORE: i32 = 10;
+
+/// Where a computer-controlled bud walks to and which way it faces to attack at the end of its turn
+pub struct Plan {
+    pub path: Vec<Point>,
+    pub direction: Direction,
+    /// Set once the bud has walked the path and turned
+    pub done: bool,
+}
+
+/// Tries every tile the bud can reach and every way it can face there, and keeps the one that
+/// hits enemies hardest, or failing that gets closest to one
+pub fn plan(
+    position: Point,
+    bud_data: &BudData,
+    collisions: &mut Collisions,
+    settings: &MatchSettings,
+    difficulty: Difficulty,
+) -> Plan {
+    let team = bud_data.initial.team;
+    let weapon = bud_data.weapon_info.weapon.clone();
+    let reach = Reach::new(position, bud_data.speed, bud_data.weight(), collisions);
+    let enemies: Vec<Point> = collisions
+        .get_colliders()
+        .iter()
+        .filter_map(|col| col.try_borrow().ok())
+        .filter(|col| {
+            col.get_tag()
+                .is_some_and(|tag| settings.is_enemy(team, tag.team))
+        })
+        .map(|col| col.get_collider())
+        .collect();
+
+    let mut rng = rand::rng();
+    let mut tiles = reach.get_tiles();
+    tiles.push(position);
+    let mut best = (i32::MIN, position, Direction::Down);
+    for tile in tiles {
+        let distance = enemies
+            .iter()
+            .map(|enemy| (enemy.x - tile.x).abs() + (enemy.y - tile.y).abs())
+            .min()
+            .unwrap_or(0);
+        for direction in [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ] {
+            let aim = weapon.aim(tile, &direction, collisions);
+            let mut damage = 0;
+            for (attack_tile, tile_damage) in weapon.get_attack_tiles(aim.origin, &direction) {
+                if !collisions
+                    .check_tag(attack_tile, bud_data.id)
+                    .is_some_and(|tag| settings.is_enemy(team, tag.team))
+                {
+                    continue;
+                }
+                let tile_damage = tile_damage as u16 + bud_data.damage;
+                damage += if difficulty.uses_flanks() {
+                    collisions
+                        .check_flank(attack_tile, &direction, bud_data.id)
+                        .unwrap_or(Flank::Front)
+                        .apply(tile_damage)
+                } else {
+                    tile_damage
+                };
+            }
+            let mut score = damage as i32 * DAMAGE_SCORE - distance;
+            if difficulty.get_noise() > 0 {
+                score += rng.random_range(0..difficulty.get_noise());
+            }
+            if score > best.0 {
+                best = (score, tile, direction);
+            }
+        }
+    }
+    let (_, goal, direction) = best;
+    Plan {
+        path: reach.path_to(goal).unwrap_or_default(),
+        direction,
+        done: false,
+    }
+}
@@ -0,0 +1,144 @@
+use sdl2::{
+    gfx::primitives::DrawRenderer,
+    pixels::Color,
+    rect::{Point, Rect},
+    render::Canvas,
+    video::Window,
+};
+
+use crate::game::{
+    camera::Camera,
+    collision_system::collisions::Collisions,
+    game_object::game_objects::bud::{
+        weapon::{Aim, Weapon},
+        Direction, Flank,
+    },
+    match_settings::MatchSettings,
+    registry::ObjectId,
+};
+
+pub struct PreviewTile {
+    pub position: Point,
+    pub damage: u16,
+    pub enemy: bool,
+    pub flank: Option<Flank>,
+}
+
+/// The bud the preview is drawn for
+pub struct Attacker<'a> {
+    pub id: ObjectId,
+    pub weapon: &'a Weapon,
+    pub direction: &'a Direction,
+    pub bonus_damage: u16,
+    pub team: u8,
+}
+
+/// The tiles a bud's weapon will hit, drawn in front of the bud
+pub struct AttackPreview {
+    tiles: Vec<PreviewTile>,
+    range_tiles: Vec<Point>,
+    blocked_tiles: Vec<Point>,
+}
+
+impl AttackPreview {
+    pub fn new() -> Self {
+        Self {
+            tiles: Vec::new(),
+            range_tiles: Vec::new(),
+            blocked_tiles: Vec::new(),
+        }
+    }
+    pub fn clear(&mut self) {
+        self.tiles.clear();
+        self.range_tiles.clear();
+        self.blocked_tiles.clear();
+    }
+    pub fn update(
+        &mut self,
+        aim: Aim,
+        attacker: Attacker,
+        collisions: &Collisions,
+        settings: &MatchSettings,
+    ) {
+        let Attacker {
+            id,
+            weapon,
+            direction,
+            bonus_damage,
+            team,
+        } = attacker;
+        self.range_tiles = aim.path;
+        self.blocked_tiles = aim.blocked_path;
+        self.tiles = weapon
+            .get_attack_tiles(aim.origin, direction)
+            .into_iter()
+            .map(|(tile, damage)| {
+                let flank = collisions.check_flank(tile, direction, id);
+                let mut damage = flank
+                    .unwrap_or(Flank::Front)
+                    .apply(damage as u16 + bonus_damage);
+                if weapon.is_ranged() {
+                    damage = collisions.get_terrain(tile).ranged_damage(damage);
+                }
+                PreviewTile {
+                    position: tile,
+                    damage,
+                    enemy: collisions
+                        .check_team(tile, id)
+                        .is_some_and(|other_team| settings.is_enemy(team, other_team)),
+                    flank,
+                }
+            })
+            .collect();
+    }
+
+    pub fn draw(&self, canvas: &mut Canvas<Window>, camera: &Camera) {
+        for tile in self.range_tiles.iter() {
+            let mut rect = Rect::from_center(*tile, 16, 16);
+            camera.rect_to_camera(&mut rect);
+            canvas.set_draw_color(Color::RGBA(255, 255, 255, 60));
+            let _ = canvas.fill_rect(rect);
+        }
+        for tile in self.blocked_tiles.iter() {
+            let mut rect = Rect::from_center(*tile, 16, 16);
+            camera.rect_to_camera(&mut rect);
+            canvas.set_draw_color(Color::RGBA(0, 0, 0, 60));
+            let _ = canvas.fill_rect(rect);
+            canvas.set_draw_color(Color::RGBA(0, 0, 0, 120));
+            let _ = canvas.draw_line(rect.top_left(), rect.bottom_right());
+        }
+        for tile in self.tiles.iter() {
+            let mut rect = Rect::from_center(tile.position, 16, 16);
+            camera.rect_to_camera(&mut rect);
+            canvas.set_draw_color(Color::RGBA(255, 0, 0, 80));
+            let _ = canvas.fill_rect(rect);
+            if tile.enemy {
+                canvas.set_draw_color(Color::RGB(255, 0, 0));
+                let _ = canvas.draw_rect(rect);
+                rect.x += 1;
+                rect.y += 1;
+                rect.w -= 2;
+                rect.h -= 2;
+                let _ = canvas.draw_rect(rect);
+            }
+            let _ = canvas.string(
+                rect.center().x as i16,
+                rect.center().y as i16,
+                &format!("{}", tile.damage),
+                Color::RGB(255, 255, 255),
+            );
+            let flank = match tile.flank {
+                Some(Flank::Side) => "Side",
+                Some(Flank::Back) => "Back",
+                Some(Flank::Blocked) => "Block",
+                Some(Flank::Front) | None => continue,
+            };
+            let _ = canvas.string(
+                rect.left() as i16 + 1,
+                rect.bottom() as i16 - 9,
+                flank,
+                Color::RGB(255, 255, 0),
+            );
+        }
+    }
+}
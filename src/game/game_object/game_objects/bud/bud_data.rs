@@ -9,37 +9,90 @@ use sdl2::{
 
 use crate::game::{
     camera::Camera,
-    effect_system::effects::{aura_effect::AuraEffect, self_effect::DamageEffect, Effect},
+    effect_system::{
+        effects::{aura_effect::AuraEffect, self_effect::DamageEffect, Effect},
+        statuses::{Duration, StatusList},
+    },
+    event_system::events::{BudTag, CombatEvent, EventBus},
     game_object::game_objects::bud::weapon::{Weapon, WeaponInfo},
     game_state::game_states::select_state::NameGenerator,
+    registry::ObjectId,
 };
 
+const HIT_FLASH_TIME: f32 = 200.0;
+const BASE_WEIGHT: i16 = 2;
+
+/// A forced move waiting to be carried out by the bud on its next update
+pub struct Displacement {
+    pub step: Point,
+    pub tiles: u8,
+    /// Whether running into something hurts both sides
+    pub impact: bool,
+    pub source: Option<BudTag>,
+}
+
 pub struct BudData<'g> {
+    pub id: ObjectId,
     pub initial: InitialBudData<'g>,
+    events: Rc<RefCell<EventBus<'g>>>,
     pub selected: bool,
     pub max_health: u16,
     pub health: u16,
     pub speed: u16,
     pub damage: u16,
+    /// The weapon fought with this round, a weapon crate swaps it without touching the roster
+    pub weapon_info: WeaponInfo<'g>,
+    pub statuses: StatusList<'g>,
+    pub displacement: Option<Displacement>,
+    /// Placeable objects left for this round
+    pub placeables: u8,
+    /// Milliseconds left of the tint shown after taking damage
+    pub hit_flash: f32,
 }
 
 impl<'g> BudData<'g> {
-    pub fn remove_health(&mut self, dmg: u16) {
-        println!("Dealt {} dmg", dmg);
-        if self.health >= dmg {
-            self.health -= dmg;
-        } else if self.health < dmg {
-            self.health = 0;
+    pub fn remove_health(&mut self, dmg: u16, source: Option<BudTag>) {
+        if !self.alive() {
+            return;
+        }
+        let dmg = dmg.min(self.health);
+        self.health -= dmg;
+        if dmg > 0 {
+            self.hit_flash = HIT_FLASH_TIME;
+        }
+        self.emit(CombatEvent::Damage {
+            target: self.tag(),
+            source: source.clone(),
+            amount: dmg,
+        });
+        if !self.alive() {
+            self.emit(CombatEvent::Death {
+                bud: self.tag(),
+                source,
+            });
         }
     }
     pub fn add_health(&mut self, health: u16) {
-        println!("Healed {} dmg", health);
-        if self.health + health > self.max_health {
-            self.health = self.max_health;
-        } else {
-            self.health += health;
+        let health = health.min(self.max_health.saturating_sub(self.health));
+        self.health += health;
+        if health > 0 {
+            self.emit(CombatEvent::Heal {
+                target: self.tag(),
+                amount: health,
+            });
         }
     }
+    pub fn tag(&self) -> BudTag {
+        BudTag {
+            id: self.id,
+            team: self.initial.team,
+            index: self.initial.index,
+            name: self.initial.name.clone(),
+        }
+    }
+    pub fn emit(&self, event: CombatEvent) {
+        self.events.borrow_mut().emit(event);
+    }
     pub fn select(&mut self) {
         self.selected = true;
     }
@@ -55,10 +108,13 @@ impl<'g> BudData<'g> {
     pub fn alive(&self) -> bool {
         self.health > 0
     }
+    pub fn weight(&self) -> u8 {
+        self.initial.get_weight_with(&self.weapon_info.weapon)
+    }
 
     pub fn draw_bud_data(&self, index: i32, canvas: &mut Canvas<Window>, camera: &Camera) {
         canvas.set_draw_color(sdl2::pixels::Color::RGB(0, 0, 0));
-        let mut point = Point::new(20 * index as i32 + 1, 80);
+        let mut point = Point::new(CARD_WIDTH * index + 1, 80);
         camera.ui_point_to_camera(&mut point);
         canvas.string(
             point.x as i16,
@@ -66,7 +122,7 @@ impl<'g> BudData<'g> {
             &format!("Health: {}/{}", self.health, self.initial.max_health),
             sdl2::pixels::Color::RGB(0, 0, 0),
         );
-        let mut point = Point::new(20 * index as i32 + 1, 82);
+        let mut point = Point::new(CARD_WIDTH * index + 1, 82);
         camera.ui_point_to_camera(&mut point);
         canvas.string(
             point.x as i16,
@@ -74,20 +130,77 @@ impl<'g> BudData<'g> {
             &format!("Speed: {}/{}", self.speed, self.initial.max_speed),
             sdl2::pixels::Color::RGB(0, 0, 0),
         );
+        let mut point = Point::new(CARD_WIDTH * index + 1, 84);
+        camera.ui_point_to_camera(&mut point);
+        let _ = canvas.string(
+            point.x as i16,
+            point.y as i16,
+            &format!("Weight: {}", self.weight()),
+            sdl2::pixels::Color::RGB(0, 0, 0),
+        );
+        for (i, status) in self.statuses.get_statuses().iter().enumerate() {
+            let mut point = Point::new(CARD_WIDTH * index + 1, 87 + 2 * i as i32);
+            camera.ui_point_to_camera(&mut point);
+            let stacks = match status.get_stacks() {
+                1 => String::new(),
+                stacks => format!(" x{}", stacks),
+            };
+            let remaining = match status.get_remaining() {
+                Duration::Turns(1) => String::from(" (1 turn)"),
+                Duration::Turns(turns) => format!(" ({} turns)", turns),
+                Duration::Permanent | Duration::Instant => String::new(),
+            };
+            let _ = canvas.string(
+                point.x as i16,
+                point.y as i16,
+                &format!("{}{}{}", status.get_name(), stacks, remaining),
+                sdl2::pixels::Color::RGB(0, 0, 0),
+            );
+        }
     }
 
-    pub fn default(initial: InitialBudData<'g>) -> BudData<'g> {
+    pub fn default(
+        initial: InitialBudData<'g>,
+        id: ObjectId,
+        events: Rc<RefCell<EventBus<'g>>>,
+    ) -> BudData<'g> {
+        let mut statuses = StatusList::new();
+        for effect in initial.effects.iter().flatten() {
+            statuses.add(effect.clone());
+        }
         BudData {
-            initial,
+            id,
+            events,
             selected: false,
-            max_health: 10,
-            health: 10,
-            speed: 3,
+            max_health: initial.max_health,
+            health: initial.max_health,
+            speed: initial.max_speed,
             damage: 0,
+            weapon_info: initial.weapon_info.clone(),
+            statuses,
+            displacement: None,
+            placeables: 1,
+            hit_flash: 0.0,
+            initial,
         }
     }
 }
 
+/// Widest a bud's card gets, the arena panels always use this width
+pub const CARD_WIDTH: i32 = 20;
+/// Trait slots every bud starts with, more can be bought between rounds
+pub const TRAIT_SLOTS: usize = 3;
+/// Most trait slots a bud can have
+pub const MAX_TRAIT_SLOTS: usize = 5;
+/// Highest level a bud can reach
+pub const MAX_LEVEL: u8 = 5;
+/// Experience needed per level to reach the next one
+const LEVEL_XP: u32 = 10;
+/// The level that unlocks an extra trait slot
+const TRAIT_SLOT_LEVEL: u8 = 3;
+/// Health gained on the even levels
+const LEVEL_HEALTH: u16 = 2;
+
 #[derive(Clone)]
 pub struct InitialBudData<'g> {
     pub texture: Rc<Texture<'g>>,
@@ -96,10 +209,20 @@ pub struct InitialBudData<'g> {
     pub index: u8,
     pub team: u8,
     pub rounds: u64,
-    pub effects: [Option<Box<dyn Effect<'g>>>; 3],
-    pub effect_textures: [Option<Rc<Texture<'g>>>; 3],
+    pub level: u8,
+    /// Experience towards the next level
+    pub xp: u32,
+    /// Enemies finished off over the whole match
+    pub kills: u32,
+    /// Damage dealt to enemies over the whole match
+    pub damage_dealt: u32,
+    /// One entry per trait slot, empty slots are `None`
+    pub effects: Vec<Option<Box<dyn Effect<'g>>>>,
+    pub effect_textures: Vec<Option<Rc<Texture<'g>>>>,
     pub name: String,
     pub weapon_info: WeaponInfo<'g>,
+    /// Picked in a draft, the bud keeps the weapon and traits it was drafted with
+    pub drafted: bool,
 }
 
 impl<'g> InitialBudData<'g> {
@@ -111,7 +234,15 @@ impl<'g> InitialBudData<'g> {
         weapon_info: WeaponInfo<'g>,
     ) -> InitialBudData<'g> {
         let name = name_generator.selectRandName();
-
+        InitialBudData::new(texture, team, index, name, weapon_info)
+    }
+    pub fn new(
+        texture: Rc<Texture<'g>>,
+        team: u8,
+        index: u8,
+        name: String,
+        weapon_info: WeaponInfo<'g>,
+    ) -> InitialBudData<'g> {
         InitialBudData {
             texture,
             max_health: 10,
@@ -119,11 +250,16 @@ impl<'g> InitialBudData<'g> {
             index,
             team,
             rounds: 0,
-            effect_textures: [None, None, None],
-            effects: [None, None, None],
+            level: 1,
+            xp: 0,
+            kills: 0,
+            damage_dealt: 0,
+            effect_textures: vec![None; TRAIT_SLOTS],
+            effects: vec![None; TRAIT_SLOTS],
             //Some(Rc::new(RefCell::new(AuraEffect::new(Box::new(DamageEffect::new(10))))))
             name,
             weapon_info,
+            drafted: false,
         }
     }
     pub fn add_effect(&mut self, new_effect: Box<dyn Effect<'g>>, tex: Option<Rc<Texture<'g>>>) {
@@ -138,8 +274,17 @@ impl<'g> InitialBudData<'g> {
         }
     }
     pub fn clear_effects(&mut self) {
-        self.effects = [None, None, None];
-        self.effect_textures = [None, None, None];
+        self.effects = vec![None; self.effects.len()];
+        self.effect_textures = vec![None; self.effect_textures.len()];
+    }
+    /// Adds an empty trait slot, false once the bud has all it can get
+    pub fn add_trait_slot(&mut self) -> bool {
+        if self.effects.len() >= MAX_TRAIT_SLOTS {
+            return false;
+        }
+        self.effects.push(None);
+        self.effect_textures.push(None);
+        true
     }
     pub fn change_weapon(
         &mut self,
@@ -149,14 +294,72 @@ impl<'g> InitialBudData<'g> {
         self.weapon_info = weapon_info;
     }
 
+    /// Points the bud takes from its team's budget
+    pub fn get_cost(&self) -> u16 {
+        let traits: u16 = self
+            .effects
+            .iter()
+            .flatten()
+            .map(|effect| effect.cost())
+            .sum();
+        self.weapon_info.weapon.get_cost() + traits
+    }
+    /// Heavier buds act later, but are harder to push and push others further
+    pub fn get_weight(&self) -> u8 {
+        self.get_weight_with(&self.weapon_info.weapon)
+    }
+    /// The weight the bud has when carrying `weapon` instead of its own
+    pub fn get_weight_with(&self, weapon: &Weapon) -> u8 {
+        let traits: i16 = self
+            .effects
+            .iter()
+            .flatten()
+            .map(|effect| effect.weight() as i16)
+            .sum();
+        (BASE_WEIGHT + weapon.get_weight() as i16 + traits).max(0) as u8
+    }
     pub fn new_round(&mut self, index: u8) {
         self.rounds += 1;
         self.index = index;
     }
+    /// Experience needed to reach the next level, `None` at the highest level
+    pub fn get_next_level_xp(&self) -> Option<u32> {
+        (self.level < MAX_LEVEL).then(|| self.level as u32 * LEVEL_XP)
+    }
+    pub fn add_xp(&mut self, xp: u32) {
+        self.xp += xp;
+        while let Some(next_level_xp) = self.get_next_level_xp() {
+            if self.xp < next_level_xp {
+                return;
+            }
+            self.xp -= next_level_xp;
+            self.level_up();
+        }
+        self.xp = 0;
+    }
+    /// Even levels add health, odd levels add speed, apart from the one that adds a trait slot
+    fn level_up(&mut self) {
+        self.level += 1;
+        match self.level {
+            TRAIT_SLOT_LEVEL => {
+                self.add_trait_slot();
+            }
+            level if level % 2 == 0 => self.max_health += LEVEL_HEALTH,
+            _ => self.max_speed += 1,
+        }
+    }
 
-    pub fn draw_initial_bud_data(&self, index: i32, canvas: &mut Canvas<Window>, camera: &Camera) {
+    /// Draws the bud's card, the `index`th of cards `width` wide
+    pub fn draw_initial_bud_data(
+        &self,
+        index: i32,
+        width: i32,
+        canvas: &mut Canvas<Window>,
+        camera: &Camera,
+    ) {
+        let x = width * index;
         canvas.set_draw_color(sdl2::pixels::Color::RGB(0, 0, 0));
-        let mut rect = Rect::new(20 * index as i32, 20, 20, 60);
+        let mut rect = Rect::new(x, 20, width as u32, 60);
         camera.ui_rect_to_camera(&mut rect);
         rect.x += 1;
         rect.y += 1;
@@ -164,7 +367,7 @@ impl<'g> InitialBudData<'g> {
         rect.h -= 2;
         canvas.draw_rect(rect);
 
-        let mut point = Point::new(20 * index as i32 + 1, 21);
+        let mut point = Point::new(x + 1, 21);
         camera.ui_point_to_camera(&mut point);
         canvas.string(
             point.x as i16,
@@ -173,7 +376,7 @@ impl<'g> InitialBudData<'g> {
             sdl2::pixels::Color::RGB(0, 0, 0),
         );
 
-        let mut point = Point::new(20 * index as i32 + 1, 24);
+        let mut point = Point::new(x + 1, 24);
         camera.ui_point_to_camera(&mut point);
         canvas.string(
             point.x as i16,
@@ -182,7 +385,7 @@ impl<'g> InitialBudData<'g> {
             sdl2::pixels::Color::RGB(0, 0, 0),
         );
 
-        let mut point = Point::new(20 * index as i32 + 1, 27);
+        let mut point = Point::new(x + 1, 27);
         camera.ui_point_to_camera(&mut point);
         canvas.string(
             point.x as i16,
@@ -191,9 +394,28 @@ impl<'g> InitialBudData<'g> {
             sdl2::pixels::Color::RGB(0, 0, 0),
         );
 
+        let mut point = Point::new(x + 1, 33);
+        camera.ui_point_to_camera(&mut point);
+        let _ = canvas.string(
+            point.x as i16,
+            point.y as i16,
+            &format!("Level {}", self.level),
+            sdl2::pixels::Color::RGB(0, 0, 0),
+        );
+        let mut bar = Rect::new(x + 1, 35, (width - 2).max(1) as u32, 2);
+        camera.ui_rect_to_camera(&mut bar);
+        let filled = match self.get_next_level_xp() {
+            Some(next_level_xp) => bar.width() * self.xp / next_level_xp,
+            None => bar.width(),
+        };
+        canvas.set_draw_color(sdl2::pixels::Color::RGB(90, 160, 230));
+        let _ = canvas.fill_rect(Rect::new(bar.x, bar.y, filled.max(1), bar.height()));
+        canvas.set_draw_color(sdl2::pixels::Color::RGB(0, 0, 0));
+        let _ = canvas.draw_rect(bar);
+
         let mut weapon_rect = self.weapon_info.weapon_rect.clone();
+        weapon_rect.x = x;
         camera.ui_rect_to_camera(&mut weapon_rect);
-        weapon_rect.x *= index;
         canvas.copy_ex(
             &self.weapon_info.weapon_texture,
             None,
@@ -203,9 +425,22 @@ impl<'g> InitialBudData<'g> {
             false,
             false,
         );
+        let upgrades = self.weapon_info.weapon.get_upgrades();
+        if upgrades > 0 {
+            let mut point = Point::new(x + 1, 30);
+            camera.ui_point_to_camera(&mut point);
+            let _ = canvas.string(
+                point.x as i16,
+                point.y as i16,
+                &format!("Weapon +{}", upgrades),
+                sdl2::pixels::Color::RGB(0, 0, 0),
+            );
+        }
 
+        // Extra trait slots shrink the icons to keep them on the card
+        let size = (width / self.effect_textures.len() as i32).min(6);
         for (i, effect_texture) in self.effect_textures.iter().enumerate() {
-            let mut rect = Rect::new(20 * index as i32 + 6 * i as i32, 40, 6, 6);
+            let mut rect = Rect::new(x + size * i as i32, 40, size as u32, size as u32);
             camera.ui_rect_to_camera(&mut rect);
             rect.x += 2;
             if let Some(effect_texture) = effect_texture {
@@ -5,18 +5,35 @@ use sdl2::{
     render::Texture,
 };
 
+use crate::game::{
+    collision_system::collisions::Collisions,
+    effect_system::effects::{
+        displace_effect::DisplaceEffect,
+        status_effect::{BleedEffect, PoisonEffect, StunEffect},
+        Effect,
+    },
+    event_system::events::BudTag,
+    game_object::game_objects::{bud::Direction, placeable::PlaceableKind},
+};
+
 #[derive(Clone)]
 pub struct Weapon {
     damage_map: Rc<Vec<(Point, u8)>>,
     range: u8,
+    /// Bought between rounds, each one adds a point of damage to every tile
+    upgrades: u8,
     pub weapon_enum: WeaponEnum,
 }
+
+/// Most upgrades a single weapon can take
+pub const MAX_UPGRADES: u8 = 2;
 impl Weapon {
     pub fn default() -> Weapon {
         Weapon {
             damage_map: Rc::new(vec![(Point::new(0, 1), 3), (Point::new(0, 2), 3)]),
             weapon_enum: WeaponEnum::Sword,
             range: 0,
+            upgrades: 0,
         }
     }
     // pub fn from_file(file: &str) -> Weapon {
@@ -60,9 +77,121 @@ impl Weapon {
                 WeaponEnum::Javelin => 3,
                 _ => 0,
             },
+            upgrades: 0,
             weapon_enum,
         }
     }
+    pub fn get_weight(&self) -> u8 {
+        match self.weapon_enum {
+            WeaponEnum::Dagger | WeaponEnum::Bow | WeaponEnum::Slingshot => 1,
+            WeaponEnum::Sword | WeaponEnum::Crossbow | WeaponEnum::Javelin => 2,
+            WeaponEnum::Axe | WeaponEnum::Shield => 3,
+        }
+    }
+    pub fn get_upgrades(&self) -> u8 {
+        self.upgrades
+    }
+    /// Adds an upgrade, false once the weapon can't take any more
+    pub fn upgrade(&mut self) -> bool {
+        if self.upgrades >= MAX_UPGRADES {
+            return false;
+        }
+        self.upgrades += 1;
+        true
+    }
+    /// Points the weapon takes from a team's budget
+    pub fn get_cost(&self) -> u16 {
+        match self.weapon_enum {
+            WeaponEnum::Dagger | WeaponEnum::Slingshot => 1,
+            WeaponEnum::Sword | WeaponEnum::Shield | WeaponEnum::Javelin => 2,
+            WeaponEnum::Axe | WeaponEnum::Bow | WeaponEnum::Crossbow => 3,
+        }
+    }
+    pub fn get_range(&self) -> u8 {
+        self.range
+    }
+    pub fn is_ranged(&self) -> bool {
+        self.range > 0
+    }
+    /// What the bud can put down in front of itself once a round
+    pub fn get_placeable(&self) -> Option<PlaceableKind> {
+        match self.weapon_enum {
+            WeaponEnum::Shield => Some(PlaceableKind::Barricade),
+            WeaponEnum::Dagger => Some(PlaceableKind::Caltrops),
+            WeaponEnum::Crossbow => Some(PlaceableKind::BearTrap),
+            _ => None,
+        }
+    }
+    /// Extra effect landing on every tile the weapon hits, next to its damage
+    pub fn on_hit<'g>(
+        &self,
+        source: BudTag,
+        direction: &Direction,
+        weight: u8,
+    ) -> Option<Box<dyn Effect<'g> + 'g>> {
+        match self.weapon_enum {
+            WeaponEnum::Axe => Some(Box::new(
+                DisplaceEffect::knockback(direction, 1)
+                    .with_weight(weight)
+                    .with_source(source),
+            )),
+            WeaponEnum::Dagger => Some(Box::new(BleedEffect::new(1, 2).with_source(source))),
+            WeaponEnum::Shield => Some(Box::new(StunEffect::new(1))),
+            WeaponEnum::Bow => Some(Box::new(PoisonEffect::new(1, 2).with_source(source))),
+            WeaponEnum::Javelin => Some(Box::new(
+                DisplaceEffect::pull(direction, 1)
+                    .with_weight(weight)
+                    .with_source(source),
+            )),
+            _ => None,
+        }
+    }
+    /// The damage map is written facing down, this turns it to face `direction`
+    pub fn get_damage_map(&self, direction: &Direction) -> Vec<(Point, u8)> {
+        self.damage_map
+            .iter()
+            .map(|(point, damage)| (direction.rotate(*point), *damage))
+            .collect()
+    }
+    pub fn get_attack_tiles(&self, origin: Point, direction: &Direction) -> Vec<(Point, u8)> {
+        self.get_damage_map(direction)
+            .into_iter()
+            .map(|(point, damage)| (origin + point, damage + self.upgrades))
+            .collect()
+    }
+    /// Melee weapons strike right in front of the bud, ranged weapons trace a shot
+    /// across the grid and strike at the first tile that blocks it, further from high ground
+    pub fn aim(&self, position: Point, direction: &Direction, collisions: &mut Collisions) -> Aim {
+        if !self.is_ranged() {
+            return Aim {
+                origin: position,
+                path: Vec::new(),
+                blocked_path: Vec::new(),
+                hit: None,
+            };
+        }
+        let range = self.range + collisions.get_terrain(position).range_bonus();
+        let end = position + direction.get_point() * range as i32;
+        let hit = collisions.raycast(position, end);
+        let line = Collisions::line(position, end);
+        let stop = hit
+            .and_then(|hit| line.iter().position(|tile| *tile == hit))
+            .unwrap_or(line.len() - 1);
+        Aim {
+            origin: hit.unwrap_or(end) - direction.get_point(),
+            path: line[1..stop].to_vec(),
+            blocked_path: line[stop + 1..].to_vec(),
+            hit,
+        }
+    }
+}
+
+/// Where a weapon's damage map lands and the line a ranged shot travels along
+pub struct Aim {
+    pub origin: Point,
+    pub path: Vec<Point>,
+    pub blocked_path: Vec<Point>,
+    pub hit: Option<Point>,
 }
 
 #[derive(Clone, Debug)]
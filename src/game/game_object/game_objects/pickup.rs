@@ -0,0 +1,245 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use rand::{seq::IndexedRandom, Rng};
+use sdl2::{
+    gfx::primitives::DrawRenderer,
+    pixels::Color,
+    rect::{Point, Rect},
+    render::{Canvas, Texture},
+    video::Window,
+};
+
+use crate::{
+    game::{
+        button::NoButton,
+        camera::Camera,
+        collision_system::collisions::{Colliding, Collisions},
+        effect_system::effects::{
+            pickup_effect::{CollectEffect, PowerUpEffect, WeaponSwapEffect},
+            self_effect::{BulwarkEffect, FighterEffect, HealEffect, MendingEffect, ScoutEffect},
+            Effect,
+        },
+        game_info::GameInfo,
+        game_object::{
+            game_objects::bud::weapon::{WeaponEnum, WeaponInfo},
+            GameObject,
+        },
+        game_state::StateInfo,
+    },
+    vector2d::Vector2d,
+};
+
+/// Health a potion gives back
+const POTION_HEALING: u16 = 3;
+/// Turns a power-up trait lasts
+const POWER_UP_TURNS: u32 = 3;
+/// Tries at finding a free tile before a random pickup is given up on
+const SPAWN_TRIES: u8 = 20;
+
+/// What a pickup gives, marked in a tile map with a letter in place of an empty tile
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PickupKind {
+    Potion,
+    PowerUp,
+    WeaponCrate,
+}
+
+impl PickupKind {
+    pub fn from_char(c: char) -> Option<PickupKind> {
+        match c {
+            'p' => Some(PickupKind::Potion),
+            'u' => Some(PickupKind::PowerUp),
+            'x' => Some(PickupKind::WeaponCrate),
+            _ => None,
+        }
+    }
+    pub fn all() -> [PickupKind; 3] {
+        [
+            PickupKind::Potion,
+            PickupKind::PowerUp,
+            PickupKind::WeaponCrate,
+        ]
+    }
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            PickupKind::Potion => "Health Potion",
+            PickupKind::PowerUp => "Power-Up",
+            PickupKind::WeaponCrate => "Weapon Crate",
+        }
+    }
+}
+
+/// Lies on a tile until a bud steps on it and takes what it gives
+pub struct Pickup<'g> {
+    position: Point,
+    kind: PickupKind,
+    reward: Box<dyn Effect<'g> + 'g>,
+    icon: Option<Rc<Texture<'g>>>,
+    taken: bool,
+}
+
+impl<'g> Pickup<'g> {
+    pub fn new(
+        position: Point,
+        kind: PickupKind,
+        reward: Box<dyn Effect<'g> + 'g>,
+        icon: Option<Rc<Texture<'g>>>,
+    ) -> Self {
+        Self {
+            position,
+            kind,
+            reward,
+            icon,
+            taken: false,
+        }
+    }
+}
+
+impl<'g> GameObject<'g> for Pickup<'g> {
+    fn get_position(&self) -> Vector2d {
+        Vector2d::new(self.position.x as f32, self.position.y as f32)
+    }
+
+    fn get_draw_values(&self) -> (Vector2d, Vector2d) {
+        (self.get_position(), Vector2d::new(1.0, 1.0))
+    }
+
+    fn draw(&self, canvas: &mut Canvas<Window>, camera: &mut Camera) {
+        let mut rect = Rect::from_center(self.position, 12, 12);
+        camera.rect_to_camera(&mut rect);
+        let center = rect.center();
+        match self.kind {
+            PickupKind::Potion => {
+                let size = rect.width() as i16 / 3;
+                canvas.set_draw_color(Color::RGB(200, 200, 200));
+                let _ = canvas.fill_rect(Rect::from_center(
+                    Point::new(center.x, center.y - size as i32),
+                    rect.width() / 5,
+                    rect.height() / 3,
+                ));
+                let _ = canvas.filled_circle(
+                    center.x as i16,
+                    center.y as i16 + size / 2,
+                    size,
+                    Color::RGB(210, 30, 50),
+                );
+            }
+            PickupKind::WeaponCrate => {
+                canvas.set_draw_color(Color::RGB(120, 80, 40));
+                let _ = canvas.fill_rect(rect);
+                canvas.set_draw_color(Color::RGB(70, 45, 20));
+                let _ = canvas.draw_rect(rect);
+            }
+            PickupKind::PowerUp => {
+                let _ = canvas.filled_circle(
+                    center.x as i16,
+                    center.y as i16,
+                    rect.width() as i16 / 2,
+                    Color::RGB(230, 200, 60),
+                );
+            }
+        }
+        if let Some(icon) = &self.icon {
+            let mut icon_rect = Rect::from_center(self.position, 8, 8);
+            camera.rect_to_camera(&mut icon_rect);
+            let _ = canvas.copy_ex(icon, None, icon_rect, 0.0, None, false, false);
+        }
+    }
+
+    fn update(
+        &mut self,
+        _delta_time: f32,
+        _collisions: &mut Collisions<'g>,
+        _gi: &mut GameInfo<'g>,
+        _si: &mut StateInfo<'g>,
+    ) -> bool {
+        !self.taken
+    }
+}
+
+impl<'g> Colliding<'g> for Pickup<'g> {
+    fn get_collider(&self) -> Point {
+        self.position
+    }
+
+    fn on_effected(
+        &mut self,
+        _effect: Box<dyn Effect<'g> + 'g>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+    }
+
+    fn is_solid(&self) -> bool {
+        false
+    }
+
+    fn on_enter(&mut self) -> Vec<Box<dyn Effect<'g> + 'g>> {
+        if self.taken {
+            return Vec::new();
+        }
+        self.taken = true;
+        vec![
+            Box::new(CollectEffect::new(self.kind.get_name())),
+            dyn_clone::clone_box(&*self.reward),
+        ]
+    }
+}
+
+impl<'g> NoButton for Pickup<'g> {}
+
+/// Makes pickups for the map and the game mode, rolling which trait or weapon they hold
+pub struct PickupSpawner<'g> {
+    icons: HashMap<String, Rc<Texture<'g>>>,
+    /// Size of the tile map, random pickups land inside it
+    area: (u32, u32),
+}
+
+impl<'g> PickupSpawner<'g> {
+    pub fn new(icons: HashMap<String, Rc<Texture<'g>>>, area: (u32, u32)) -> Self {
+        Self { icons, area }
+    }
+    pub fn make(&self, position: Point, kind: PickupKind) -> Pickup<'g> {
+        let mut rng = rand::rng();
+        let (reward, icon): (Box<dyn Effect<'g> + 'g>, _) = match kind {
+            PickupKind::Potion => (Box::new(HealEffect::new(POTION_HEALING)), None),
+            PickupKind::PowerUp => {
+                let effect: Box<dyn Effect<'g>> = match rng.random_range(0..4) {
+                    0 => Box::new(FighterEffect::new()),
+                    1 => Box::new(BulwarkEffect::new()),
+                    2 => Box::new(ScoutEffect::new()),
+                    _ => Box::new(MendingEffect::new()),
+                };
+                let icon = self.icons.get(&effect.name().to_lowercase()).cloned();
+                (Box::new(PowerUpEffect::new(effect, POWER_UP_TURNS)), icon)
+            }
+            PickupKind::WeaponCrate => {
+                let (weapon, weapon_string, weapon_rect) =
+                    WeaponEnum::get_weapon(rng.random_range(0..8));
+                let icon = Rc::clone(self.icons.get(&weapon_string).unwrap());
+                (
+                    Box::new(WeaponSwapEffect::new(WeaponInfo::new(
+                        weapon,
+                        Rc::clone(&icon),
+                        weapon_rect,
+                    ))),
+                    Some(icon),
+                )
+            }
+        };
+        Pickup::new(position, kind, reward, icon)
+    }
+    /// A pickup of a random kind on a free tile of the map, if one turns up
+    pub fn random(&self, collisions: &Collisions<'g>) -> Option<Pickup<'g>> {
+        let mut rng = rand::rng();
+        let kind = *PickupKind::all().choose(&mut rng).unwrap();
+        (0..SPAWN_TRIES)
+            .map(|_| {
+                Point::new(
+                    rng.random_range(0..self.area.0) as i32,
+                    rng.random_range(0..self.area.1) as i32,
+                )
+            })
+            .find(|tile| collisions.get_at(*tile).is_empty() && collisions.can_enter(*tile, 0))
+            .map(|tile| self.make(tile, kind))
+    }
+}
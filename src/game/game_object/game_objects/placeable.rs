@@ -0,0 +1,180 @@
+use std::{cell::RefCell, rc::Rc};
+
+use sdl2::{
+    gfx::primitives::DrawRenderer,
+    pixels::Color,
+    rect::{Point, Rect},
+    render::Canvas,
+    video::Window,
+};
+
+use crate::{
+    game::{
+        button::NoButton,
+        camera::Camera,
+        collision_system::collisions::{Colliding, Collisions},
+        effect_system::effects::{self_effect::DamageEffect, status_effect::RootEffect, Effect},
+        event_system::events::BudTag,
+        game_info::GameInfo,
+        game_object::GameObject,
+        game_state::StateInfo,
+    },
+    vector2d::Vector2d,
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PlaceableKind {
+    /// Hurts every bud that steps on them
+    Caltrops,
+    /// Hurts and roots the first bud that steps on it, then it is gone
+    BearTrap,
+    /// Blocks the tile until it is broken
+    Barricade,
+}
+
+impl PlaceableKind {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            PlaceableKind::Caltrops => "Caltrops",
+            PlaceableKind::BearTrap => "Bear Trap",
+            PlaceableKind::Barricade => "Barricade",
+        }
+    }
+    /// How many turns it stays on the field
+    pub fn get_turns(&self) -> u32 {
+        match self {
+            PlaceableKind::Caltrops => 6,
+            PlaceableKind::BearTrap => 10,
+            PlaceableKind::Barricade => 8,
+        }
+    }
+}
+
+/// Something a bud puts down during a match, gone once it runs out of turns, health or uses
+pub struct Placeable {
+    position: Point,
+    kind: PlaceableKind,
+    /// The turn it disappears on
+    expires: u32,
+    health: u16,
+    spent: bool,
+    source: BudTag,
+}
+
+impl Placeable {
+    pub fn new(position: Point, kind: PlaceableKind, turn: u32, source: BudTag) -> Self {
+        Self {
+            position,
+            kind,
+            expires: turn + kind.get_turns(),
+            health: match kind {
+                PlaceableKind::Barricade => 4,
+                _ => 1,
+            },
+            spent: false,
+            source,
+        }
+    }
+}
+
+impl<'g> GameObject<'g> for Placeable {
+    fn get_position(&self) -> Vector2d {
+        Vector2d::new(self.position.x as f32, self.position.y as f32)
+    }
+
+    fn get_draw_values(&self) -> (Vector2d, Vector2d) {
+        (self.get_position(), Vector2d::new(1.0, 1.0))
+    }
+
+    fn draw(&self, canvas: &mut Canvas<Window>, camera: &mut Camera) {
+        let mut rect = Rect::from_center(self.position, 16, 16);
+        camera.rect_to_camera(&mut rect);
+        let center = rect.center();
+        let size = rect.width() as i16 / 4;
+        match self.kind {
+            PlaceableKind::Caltrops => {
+                for offset in [Point::new(-1, -1), Point::new(1, 0), Point::new(-1, 1)] {
+                    let x = center.x as i16 + offset.x as i16 * size;
+                    let y = center.y as i16 + offset.y as i16 * size;
+                    let _ = canvas.line(x - 3, y - 3, x + 3, y + 3, Color::RGB(90, 90, 90));
+                    let _ = canvas.line(x - 3, y + 3, x + 3, y - 3, Color::RGB(90, 90, 90));
+                }
+            }
+            PlaceableKind::BearTrap => {
+                let _ = canvas.circle(
+                    center.x as i16,
+                    center.y as i16,
+                    size + 2,
+                    Color::RGB(60, 60, 60),
+                );
+                let _ = canvas.circle(
+                    center.x as i16,
+                    center.y as i16,
+                    size,
+                    Color::RGB(60, 60, 60),
+                );
+            }
+            PlaceableKind::Barricade => {
+                canvas.set_draw_color(Color::RGB(120, 80, 40));
+                let _ =
+                    canvas.fill_rect(Rect::from_center(center, rect.width(), rect.height() / 2));
+                canvas.set_draw_color(Color::RGB(70, 45, 20));
+                let _ = canvas.draw_line(rect.top_left(), rect.bottom_right());
+                let _ = canvas.draw_line(rect.bottom_left(), rect.top_right());
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        _delta_time: f32,
+        _collisions: &mut Collisions<'g>,
+        _gi: &mut GameInfo<'g>,
+        si: &mut StateInfo<'g>,
+    ) -> bool {
+        !self.spent && self.health > 0 && si.events.borrow().get_turn() < self.expires
+    }
+}
+
+impl<'g> Colliding<'g> for Placeable {
+    fn get_collider(&self) -> Point {
+        self.position
+    }
+
+    fn on_effected(
+        &mut self,
+        effect: Box<dyn Effect<'g> + 'g>,
+        _others: Vec<Rc<RefCell<dyn Colliding<'g> + 'g>>>,
+    ) {
+        if self.kind == PlaceableKind::Barricade {
+            self.health = self.health.saturating_sub(effect.damage());
+        }
+    }
+
+    fn is_solid(&self) -> bool {
+        self.kind == PlaceableKind::Barricade && self.health > 0
+    }
+
+    fn on_enter(&mut self) -> Vec<Box<dyn Effect<'g> + 'g>> {
+        if self.spent {
+            return Vec::new();
+        }
+        match self.kind {
+            PlaceableKind::Caltrops => {
+                vec![Box::new(
+                    DamageEffect::new(1).with_source(self.source.clone()),
+                )]
+            }
+            PlaceableKind::BearTrap => {
+                self.spent = true;
+                vec![
+                    Box::new(DamageEffect::new(2).with_source(self.source.clone())),
+                    Box::new(RootEffect::new(1)),
+                ]
+            }
+            PlaceableKind::Barricade => Vec::new(),
+        }
+    }
+}
+
+impl NoButton for Placeable {}
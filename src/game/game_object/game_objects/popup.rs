@@ -0,0 +1,120 @@
+use std::{cell::RefCell, rc::Rc};
+
+use sdl2::{
+    gfx::primitives::DrawRenderer,
+    pixels::Color,
+    rect::{Point, Rect},
+    render::Canvas,
+    video::Window,
+};
+
+use crate::{
+    game::{
+        button::NoButton,
+        camera::Camera,
+        collision_system::collisions::Collisions,
+        event_system::events::{CombatEvent, EventListener},
+        game_info::GameInfo,
+        game_object::GameObject,
+        game_state::StateInfo,
+        registry::ObjectId,
+    },
+    vector2d::Vector2d,
+};
+
+/// How long a popup stays on screen, in milliseconds
+const POPUP_TIME: f32 = 900.0;
+
+/// A number floating up from a tile, gone once its time runs out
+pub struct Popup {
+    position: Point,
+    text: String,
+    color: Color,
+    time: f32,
+}
+
+impl Popup {
+    pub fn new(position: Point, text: String, color: Color) -> Self {
+        Self {
+            position,
+            text,
+            color,
+            time: 0.0,
+        }
+    }
+}
+
+impl<'g> GameObject<'g> for Popup {
+    fn get_position(&self) -> Vector2d {
+        Vector2d::new(self.position.x as f32, self.position.y as f32)
+    }
+
+    fn get_draw_values(&self) -> (Vector2d, Vector2d) {
+        (self.get_position(), Vector2d::new(1.0, 1.0))
+    }
+
+    fn draw(&self, canvas: &mut Canvas<Window>, camera: &mut Camera) {
+        let progress = (self.time / POPUP_TIME).min(1.0);
+        let mut rect = Rect::from_center(self.position, 16, 16);
+        camera.rect_to_camera(&mut rect);
+        let rise = (progress * rect.height() as f32) as i32;
+        let alpha = (255.0 * (1.0 - progress)) as u8;
+        let _ = canvas.string(
+            rect.center().x as i16 - 4 * self.text.len() as i16,
+            (rect.top() - rise) as i16,
+            &self.text,
+            Color::RGBA(self.color.r, self.color.g, self.color.b, alpha),
+        );
+    }
+
+    fn update(
+        &mut self,
+        delta_time: f32,
+        _collisions: &mut Collisions<'g>,
+        _gi: &mut GameInfo<'g>,
+        _si: &mut StateInfo<'g>,
+    ) -> bool {
+        self.time += delta_time;
+        self.time < POPUP_TIME
+    }
+}
+
+impl NoButton for Popup {}
+
+/// Turns damage and heal events into popups above whoever took them
+pub struct PopupSpawner {
+    pending: Vec<(ObjectId, String, Color)>,
+}
+
+impl PopupSpawner {
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+        }
+    }
+    /// Adds the popups collected since the last call to the scene,
+    /// must run before the hit buds are removed so their tiles are still known
+    pub fn spawn(&mut self, collisions: &Collisions, si: &mut StateInfo) {
+        for (id, text, color) in self.pending.drain(0..) {
+            if let Some(position) = collisions.get_position(id) {
+                si.add_object(Rc::new(RefCell::new(Popup::new(position, text, color))));
+            }
+        }
+    }
+}
+
+impl EventListener for PopupSpawner {
+    fn on_event(&mut self, _turn: u32, event: &CombatEvent) {
+        match event {
+            CombatEvent::Damage { target, amount, .. } if *amount > 0 => {
+                self.pending
+                    .push((target.id, format!("-{}", amount), Color::RGB(220, 30, 30)));
+            }
+            CombatEvent::Heal { target, amount } => {
+                self.pending
+                    .push((target.id, format!("+{}", amount), Color::RGB(30, 200, 60)));
+            }
+            _ => {}
+        }
+    }
+}
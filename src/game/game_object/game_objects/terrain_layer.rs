@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use sdl2::{
+    gfx::primitives::DrawRenderer,
+    mouse::MouseState,
+    pixels::Color,
+    rect::{Point, Rect},
+    render::{Canvas, Texture},
+    video::Window,
+};
+
+use crate::{
+    game::{
+        button::Button, camera::Camera, creating_levels::terrain::Terrain, game_object::GameObject,
+        menu::menu_state::menu_states::MenuStateHandler,
+    },
+    vector2d::Vector2d,
+};
+
+/// Draws the terrain over the ground and describes the tile under the mouse
+pub struct TerrainLayer<'g> {
+    tiles: Vec<(Point, Terrain)>,
+    textures: HashMap<Terrain, Texture<'g>>,
+    hovered: Option<(Point, Terrain)>,
+}
+
+impl<'g> TerrainLayer<'g> {
+    pub fn new(tiles: Vec<(Point, Terrain)>, textures: HashMap<Terrain, Texture<'g>>) -> Self {
+        Self {
+            tiles,
+            textures,
+            hovered: None,
+        }
+    }
+}
+
+impl<'g> GameObject<'g> for TerrainLayer<'g> {
+    fn get_position(&self) -> Vector2d {
+        Vector2d::new(0.0, 0.0)
+    }
+
+    fn get_draw_values(&self) -> (Vector2d, Vector2d) {
+        (self.get_position(), Vector2d::new(1.0, 1.0))
+    }
+
+    fn draw(&self, canvas: &mut Canvas<Window>, camera: &mut Camera) {
+        for (tile, terrain) in self.tiles.iter() {
+            let mut rect = Rect::from_center(*tile, 16, 16);
+            camera.rect_to_camera(&mut rect);
+            if let Some(texture) = self.textures.get(terrain) {
+                let _ = canvas.copy_ex(texture, None, rect, 0.0, None, false, false);
+            }
+        }
+        if let Some((tile, terrain)) = self.hovered {
+            let mut rect = Rect::from_center(tile, 16, 16);
+            camera.rect_to_camera(&mut rect);
+            canvas.set_draw_color(Color::RGB(255, 255, 255));
+            let _ = canvas.draw_rect(rect);
+            let _ = canvas.string(
+                rect.right() as i16 + 4,
+                rect.top() as i16,
+                terrain.get_name(),
+                Color::RGB(255, 255, 255),
+            );
+            let _ = canvas.string(
+                rect.right() as i16 + 4,
+                rect.top() as i16 + 10,
+                terrain.get_description(),
+                Color::RGB(255, 255, 255),
+            );
+        }
+    }
+}
+
+impl<'g> Button<'g> for TerrainLayer<'g> {
+    fn get_pressed(&self) -> (bool, bool) {
+        (self.hovered.is_some(), false)
+    }
+
+    fn set_hovered(&mut self, _hovered: bool) {}
+
+    fn set_pressed(&mut self, _pressed: bool) {}
+
+    fn get_draw_values(&self) -> (Rect, &str) {
+        (Rect::new(0, 0, 0, 0), "")
+    }
+
+    fn action(&mut self, _input: &mut Self::Input) {}
+
+    /// Only remembers which terrain tile the mouse is over, for the tooltip
+    fn press(
+        &mut self,
+        mouse_state: &MouseState,
+        _button_input: &mut Self::Input,
+        camera: Option<&Camera>,
+    ) -> (bool, bool) {
+        self.hovered = camera.and_then(|camera| {
+            self.tiles.iter().copied().find(|(tile, _)| {
+                let mut rect = Rect::from_center(*tile, 16, 16);
+                camera.rect_to_camera(&mut rect);
+                rect.contains_point(Point::new(mouse_state.x(), mouse_state.y()))
+            })
+        });
+        self.get_pressed()
+    }
+
+    type Input = MenuStateHandler<'g>;
+}
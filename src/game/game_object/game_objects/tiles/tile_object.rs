@@ -10,8 +10,17 @@ use sdl2::{
 
 use crate::{
     game::{
-        button::NoButton, camera::Camera, collision_system::collisions::Colliding,
-        effect_system::effects::Tile, game_info::GameInfo, game_object::GameObject,
+        button::NoButton,
+        camera::Camera,
+        collision_system::collisions::{Colliding, Collisions},
+        creating_levels::map::TileBehavior,
+        effect_system::effects::{
+            self_effect::{CleanseEffect, DamageEffect, HealEffect},
+            Effect, Tile,
+        },
+        game_info::GameInfo,
+        game_object::GameObject,
+        game_state::StateInfo,
     },
     vector2d::Vector2d,
 };
@@ -23,6 +32,12 @@ pub struct TileObject<'g> {
     texture: Rc<RefCell<Texture<'g>>>,
     id: &'g str,
     src: Rect,
+    hp: Option<u16>,
+    max_hp: Option<u16>,
+    /// `Some(open)` for doors
+    door: Option<bool>,
+    solid: bool,
+    tile: Tile<'g>,
 }
 
 impl<'g> TileObject<'g> {
@@ -34,6 +49,7 @@ impl<'g> TileObject<'g> {
         texture: Rc<RefCell<Texture<'g>>>,
         _has_col: bool,
         src_pos: Vector2d,
+        behavior: &TileBehavior,
     ) -> Self {
         let src = Rect::new(src_pos.x as i32, src_pos.y as i32, 16, 16);
         let mut p = position.clone();
@@ -42,12 +58,25 @@ impl<'g> TileObject<'g> {
         s.y *= 1.0;
         let _pos = Rc::new(RefCell::new(p));
         let __pos = Rc::clone(&_pos);
+        let mut tile = Tile::new();
+        if let Some(damage) = behavior.spikes {
+            tile.add_effect(Box::new(DamageEffect::new(damage)));
+        }
+        if let Some(healing) = behavior.shrine {
+            tile.add_effect(Box::new(CleanseEffect::new()));
+            tile.add_effect(Box::new(HealEffect::new(healing)));
+        }
         Self {
             position: _pos,
             size: s,
             texture: Rc::clone(&texture),
             id: "TileObject",
             src,
+            hp: behavior.hp,
+            max_hp: behavior.hp,
+            door: behavior.door.then_some(false),
+            solid: behavior.spikes.is_none() && behavior.shrine.is_none(),
+            tile,
         }
     }
 }
@@ -80,15 +109,50 @@ impl<'g> GameObject<'g> for TileObject<'g> {
         //     100, //(self.size.y * 400.0) as u32,
         // );
         camera.rect_to_camera(&mut some_rect);
-        canvas.copy_ex(
-            &self.texture.borrow_mut(),
-            self.src,
-            some_rect,
-            0.0,
-            None,
-            false,
-            false,
-        );
+        // Spikes and shrines are drawn as a marker over the floor instead of a sprite
+        if !self.solid {
+            let color = if self
+                .tile
+                .get_effects()
+                .iter()
+                .any(|effect| effect.damage() > 0)
+            {
+                Color::RGBA(200, 40, 40, 120)
+            } else {
+                Color::RGBA(40, 160, 60, 120)
+            };
+            canvas.set_draw_color(color);
+            let _ = canvas.fill_rect(some_rect);
+            let _ = canvas.draw_rect(some_rect);
+            return;
+        }
+        let mut texture = self.texture.borrow_mut();
+        if self.door == Some(true) {
+            texture.set_alpha_mod(80);
+        }
+        let _ = canvas.copy_ex(&texture, self.src, some_rect, 0.0, None, false, false);
+        texture.set_alpha_mod(255);
+        if let (Some(hp), Some(max_hp)) = (self.hp, self.max_hp) {
+            canvas.set_draw_color(Color::RGB(0, 0, 0));
+            let cracks = 3 * (max_hp - hp) as i32 / max_hp.max(1) as i32;
+            for crack in 0..cracks {
+                let x = some_rect.x + some_rect.width() as i32 * (crack + 1) / 4;
+                let _ = canvas.draw_line(
+                    Point::new(x, some_rect.top()),
+                    Point::new(x - some_rect.width() as i32 / 8, some_rect.bottom()),
+                );
+            }
+        }
+    }
+    /// A broken tile is done, which leaves the floor underneath
+    fn update(
+        &mut self,
+        _delta_time: f32,
+        _collisions: &mut Collisions<'g>,
+        _gi: &mut GameInfo<'g>,
+        _si: &mut StateInfo<'g>,
+    ) -> bool {
+        self.hp != Some(0)
     }
 
     fn get_draw_values(&self) -> (Vector2d, Vector2d) {
@@ -107,10 +171,26 @@ impl<'r> Colliding<'r> for TileObject<'r> {
 
     fn on_effected(
         &mut self,
-        effect: Box<dyn crate::game::effect_system::effects::Effect<'r> + 'r>,
+        effect: Box<dyn Effect<'r> + 'r>,
         others: Vec<Rc<RefCell<dyn Colliding<'r> + 'r>>>,
     ) {
-        // todo!()
+        if effect.damage() == 0 {
+            return;
+        }
+        if let Some(hp) = &mut self.hp {
+            *hp = hp.saturating_sub(effect.damage());
+        }
+        if let Some(open) = &mut self.door {
+            *open = true;
+        }
+    }
+
+    fn is_solid(&self) -> bool {
+        self.solid && self.door != Some(true) && self.hp != Some(0)
+    }
+
+    fn get_tile_effects(&self) -> Vec<Box<dyn Effect<'r> + 'r>> {
+        self.tile.get_effects()
     }
 }
 
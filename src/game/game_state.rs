@@ -3,10 +3,20 @@ use std::{cell::RefCell, rc::Rc};
 use sdl2::{render::Canvas, video::Window, EventPump};
 
 use crate::game::{
+    collision_system::collisions::{Colliding, Collisions},
+    event_system::{
+        events::EventBus,
+        gold_ledger::GoldLedger,
+        xp_ledger::{XpLedger, SURVIVE_XP},
+    },
     game_info::GameInfo,
-    game_object::game_objects::bud::bud_data::BudData,
+    game_object::{game_objects::bud::bud_data::BudData, SuperGameObject},
     game_state::game_states::GameStateEnum,
+    match_info::BudRecord,
+    menu::menu_state::menu_states::MenuStateHandler,
+    registry::{ObjectId, Registry},
     scene_manager::{Object, SceneManager},
+    turn_system::turns::TurnHandler,
 };
 
 pub mod game_states;
@@ -24,45 +34,121 @@ pub trait GameState<'g> {
 }
 
 pub struct StateInfo<'g> {
-    add_list: Vec<Rc<RefCell<Object<'g>>>>,
-    pub bud_data_tuple: (Vec<Rc<RefCell<BudData<'g>>>>, Vec<Rc<RefCell<BudData<'g>>>>),
+    add_list: Vec<(ObjectId, Rc<RefCell<Object<'g>>>)>,
+    collider_add_list: Vec<(ObjectId, Rc<RefCell<dyn Colliding<'g> + 'g>>)>,
+    pub registry: Registry,
+    pub events: Rc<RefCell<EventBus<'g>>>,
+    /// The buds of every team, indexed by team
+    pub bud_data_teams: Vec<Vec<Rc<RefCell<BudData<'g>>>>>,
+    pub gold_ledger: Rc<RefCell<GoldLedger>>,
+    pub xp_ledger: Rc<RefCell<XpLedger>>,
+    /// The bud whose turn it is is played by the computer, so the players can't end its turn
+    pub computer_turn: bool,
+    /// Set by a computer-controlled bud once it is done with its turn
+    pub end_turn: bool,
 }
 
 impl<'g> StateInfo<'g> {
     pub fn new() -> Self {
         Self {
             add_list: Vec::new(),
-            bud_data_tuple: (Vec::new(), Vec::new()),
+            collider_add_list: Vec::new(),
+            registry: Registry::new(),
+            events: Rc::new(RefCell::new(EventBus::new())),
+            bud_data_teams: Vec::new(),
+            gold_ledger: Rc::new(RefCell::new(GoldLedger::new())),
+            xp_ledger: Rc::new(RefCell::new(XpLedger::new())),
+            computer_turn: false,
+            end_turn: false,
         }
     }
-    pub fn add_object(&mut self, object: Rc<RefCell<Object<'g>>>) {
-        self.add_list.push(object);
+    pub fn add_object(&mut self, object: Rc<RefCell<Object<'g>>>) -> ObjectId {
+        let id = self.registry.new_id();
+        self.add_list.push((id, object));
+        id
     }
-    pub fn add_objects(&mut self, scene_manager: &mut SceneManager<'g>) {
+    /// For objects that take up a tile, so they are added to `Collisions` as well
+    pub fn add_colliding_object<T>(&mut self, object: Rc<RefCell<T>>) -> ObjectId
+    where
+        T: SuperGameObject<'g, Input = MenuStateHandler<'g>> + Colliding<'g> + 'g,
+    {
+        let id = self.add_object(Rc::clone(&object) as Rc<RefCell<Object<'g>>>);
+        self.collider_add_list.push((id, object));
+        id
+    }
+    pub fn add_objects(
+        &mut self,
+        scene_manager: &mut SceneManager<'g>,
+        collisions: &mut Collisions<'g>,
+    ) {
         scene_manager.object_list.extend(self.add_list.drain(0..));
+        for (id, collider) in self.collider_add_list.drain(0..) {
+            collisions.add(id, collider);
+        }
     }
-    pub fn end_round(&self, gi: &mut GameInfo<'g>) {
-        let mut initial_buds_tuple = (Vec::new(), Vec::new());
-        let mut index = 0;
-        for bud_data in self.bud_data_tuple.0.iter() {
-            let mut initial_bud_data = bud_data.borrow().initial.clone();
-            if bud_data.borrow().alive() {
-                initial_bud_data.new_round(index);
-                initial_buds_tuple.0.push(initial_bud_data);
-                index += 1;
-            }
+    /// Drops everything the registry marked as removed from every list at once
+    pub fn remove_objects(
+        &mut self,
+        scene_manager: &mut SceneManager<'g>,
+        collisions: &mut Collisions<'g>,
+        turn_handler: &mut TurnHandler<'g>,
+    ) {
+        let removed = self.registry.take_removed();
+        if removed.is_empty() {
+            return;
         }
-        let mut index = 0;
-        for bud_data in self.bud_data_tuple.1.iter() {
-            let mut initial_bud_data = bud_data.borrow().initial.clone();
-            if bud_data.borrow().alive() {
-                initial_bud_data.new_round(index);
-                initial_buds_tuple.1.push(initial_bud_data);
-                index += 1;
+        scene_manager.remove_all(&removed);
+        collisions.remove_all(&removed);
+        turn_handler.remove_all(&removed);
+    }
+    /// Pays out the round's gold and goes on with the survivors, through the standings
+    /// when the match is a tournament
+    pub fn end_round(&self, gi: &mut GameInfo<'g>, winners: &[u8]) {
+        let mut initial_bud_teams = Vec::new();
+        let mut survivors = Vec::new();
+        for bud_datas in self.bud_data_teams.iter() {
+            let mut initial_buds = Vec::new();
+            let mut index = 0;
+            for bud_data in bud_datas.iter() {
+                let mut initial_bud_data = bud_data.borrow().initial.clone();
+                let stats = self.xp_ledger.borrow().get_stats(bud_data.borrow().id);
+                initial_bud_data.kills += stats.kills;
+                initial_bud_data.damage_dealt += stats.damage;
+                if bud_data.borrow().alive() {
+                    initial_bud_data.new_round(index);
+                    initial_bud_data.add_xp(stats.get_xp() + SURVIVE_XP);
+                    initial_buds.push(initial_bud_data);
+                    index += 1;
+                } else {
+                    gi.match_info.fallen.push(BudRecord::new(&initial_bud_data));
+                }
             }
+            survivors.push(index as u32);
+            initial_bud_teams.push(initial_buds);
+        }
+        self.gold_ledger
+            .borrow()
+            .pay_out(&mut gi.match_info, &survivors, winners);
+        gi.match_info.round += 1;
+        gi.match_info.add_wins(winners);
+        if let Some(campaign) = gi.campaign.take() {
+            let survivors = initial_bud_teams.first().map_or(&[][..], |buds| buds);
+            gi.match_settings = campaign.finish(winners.contains(&0), survivors);
+            gi.game_state_handler.new_state(GameStateEnum::Campaign);
+            return;
+        }
+        if let Some(scenario) = gi.scenario.take() {
+            gi.match_settings = scenario.finish(winners.contains(&0));
+            gi.game_state_handler.new_state(GameStateEnum::Scenarios);
+            return;
+        }
+        if gi.match_settings.rounds.is_some() {
+            gi.game_state_handler
+                .new_state(GameStateEnum::Standings(initial_bud_teams));
+        } else {
+            gi.game_state_handler
+                .new_state(GameStateEnum::Select(initial_bud_teams));
         }
-        gi.game_state_handler
-            .new_state(GameStateEnum::Select(initial_buds_tuple));
     }
 }
 
@@ -5,12 +5,20 @@ use crate::game::{
 };
 
 pub mod arena_state;
+pub mod campaign_state;
 pub mod home_state;
+pub mod podium_state;
+pub mod scenario_state;
 pub mod select_state;
+pub mod standings_state;
 
 use arena_state::ArenaState;
+use campaign_state::CampaignState;
 use home_state::HomeState;
+use podium_state::PodiumState;
+use scenario_state::ScenarioState;
 use select_state::SelectState;
+use standings_state::StandingsState;
 
 pub struct GameStateHandler<'g> {
     pub game_state_fns:
@@ -30,13 +38,23 @@ impl<'g> GameStateHandler<'g> {
             Box::new(HomeState::new_state),
         );
         game_state_fns.insert(
-            GameStateEnum::Arena((Vec::new(), Vec::new())),
+            GameStateEnum::Arena(Vec::new()),
             Box::new(ArenaState::new_state),
         );
         game_state_fns.insert(
-            GameStateEnum::Select((Vec::new(), Vec::new())),
+            GameStateEnum::Select(Vec::new()),
             Box::new(SelectState::new_state),
         );
+        game_state_fns.insert(GameStateEnum::Campaign, Box::new(CampaignState::new_state));
+        game_state_fns.insert(GameStateEnum::Scenarios, Box::new(ScenarioState::new_state));
+        game_state_fns.insert(
+            GameStateEnum::Standings(Vec::new()),
+            Box::new(StandingsState::new_state),
+        );
+        game_state_fns.insert(
+            GameStateEnum::Podium(Vec::new()),
+            Box::new(PodiumState::new_state),
+        );
 
         GameStateHandler {
             new_state: None,
@@ -65,8 +83,16 @@ impl<'g> GameStateHandler<'g> {
 // #[derive(Eq)]
 pub enum GameStateEnum<'g> {
     Home(sdl2::pixels::Color),
-    Select((Vec<InitialBudData<'g>>, Vec<InitialBudData<'g>>)),
-    Arena((Vec<InitialBudData<'g>>, Vec<InitialBudData<'g>>)),
+    /// The buds of every team, indexed by team
+    Select(Vec<Vec<InitialBudData<'g>>>),
+    Arena(Vec<Vec<InitialBudData<'g>>>),
+    /// The survivors, carried on to the next round
+    Standings(Vec<Vec<InitialBudData<'g>>>),
+    /// The survivors, who count towards the MVP
+    Podium(Vec<Vec<InitialBudData<'g>>>),
+    Campaign,
+    /// The scenario browser
+    Scenarios,
 }
 
 impl<'g> Eq for GameStateEnum<'g> {}
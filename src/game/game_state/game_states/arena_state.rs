@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use sdl2::{
     gfx::primitives::DrawRenderer,
@@ -15,8 +15,9 @@ use crate::{
     game::{
         button::{Button, MenuButton},
         collision_system::collisions::Collisions,
-        creating_levels::map::Map,
-        game_info::GameInfo,
+        creating_levels::{map::Map, terrain::Terrain},
+        event_system::events::EventListener,
+        game_info::{make_map, GameInfo},
         game_object::{
             game_objects::{
                 bud::{
@@ -24,6 +25,9 @@ use crate::{
                     Bud,
                 },
                 ground::Ground,
+                pickup::PickupSpawner,
+                popup::PopupSpawner,
+                terrain_layer::TerrainLayer,
             },
             GameObject,
         },
@@ -31,11 +35,16 @@ use crate::{
             game_states::{arena_state, GameStateEnum},
             GameState, StateInfo,
         },
+        match_settings::MatchSettings,
         menu::menu_state::menu_states::{
-            bud_state::BudState, BudEnum, MenuStateEnum, MenuStateHandler,
+            bud_state::BudState, combat_log_state::CombatLogState, BudEnum, MenuStateEnum,
+            MenuStateHandler,
         },
         scene_manager::SceneManager,
-        turn_system::{game_modes::death_match::DeathMatch, turns::TurnHandler},
+        turn_system::{
+            game_modes::{death_match::DeathMatch, sudden_death::SuddenDeath, GameMode},
+            turns::TurnHandler,
+        },
     },
     vector2d::Vector2d,
 };
@@ -49,20 +58,34 @@ pub struct ArenaState<'g> {
     turn_handler: TurnHandler<'g>,
     view: View,
     end_turn: bool,
-    initial_buds_tuple: (Vec<InitialBudData<'g>>, Vec<InitialBudData<'g>>),
-    game_mode: DeathMatch,
+    toggle_log: bool,
+    popups: Rc<RefCell<PopupSpawner>>,
+    pickups: PickupSpawner<'g>,
+    initial_bud_teams: Vec<Vec<InitialBudData<'g>>>,
+    game_mode: Rc<RefCell<DeathMatch>>,
 }
 
 impl<'g> ArenaState<'g> {
-    pub fn new(initial_buds_tuple: (Vec<InitialBudData<'g>>, Vec<InitialBudData<'g>>)) -> Self {
+    pub fn new(initial_bud_teams: Vec<Vec<InitialBudData<'g>>>) -> Self {
+        let team_sizes = initial_bud_teams
+            .iter()
+            .map(|initial_buds| initial_buds.len() as u8)
+            .collect();
         Self {
             scene_manager: SceneManager::new(),
             si: StateInfo::new(),
-            collisions: Collisions::new(Vec::new()),
+            collisions: Collisions::new(),
             button: MenuButton::new(
                 Rect::new(0, 0, 40, 20),
                 "Back",
                 Box::new(|gi| {
+                    // Walking out of a campaign battle or a scenario leaves it as it was
+                    if let Some(campaign) = gi.campaign.take() {
+                        gi.match_settings = campaign.abandon();
+                    }
+                    if let Some(scenario) = gi.scenario.take() {
+                        gi.match_settings = scenario.abandon();
+                    }
                     gi.game_state_handler
                         .new_state(GameStateEnum::Home(sdl2::pixels::Color::RGB(0, 255, 0)));
                 }),
@@ -71,14 +94,17 @@ impl<'g> ArenaState<'g> {
             turn_handler: TurnHandler::new(),
             view: View::new(),
             end_turn: true,
-            initial_buds_tuple: initial_buds_tuple.clone(),
-            game_mode: DeathMatch::random(),
+            toggle_log: true,
+            popups: Rc::new(RefCell::new(PopupSpawner::new())),
+            pickups: PickupSpawner::new(HashMap::new(), (0, 0)),
+            initial_bud_teams: initial_bud_teams.clone(),
+            game_mode: Rc::new(RefCell::new(DeathMatch::random(team_sizes))),
         }
     }
     pub fn new_state(state: &GameStateEnum<'g>) -> Box<dyn GameState<'g> + 'g> {
         match state {
-            GameStateEnum::Arena(initial_buds_tuple) => {
-                Box::new(Self::new(initial_buds_tuple.clone()))
+            GameStateEnum::Arena(initial_bud_teams) => {
+                Box::new(Self::new(initial_bud_teams.clone()))
             }
             _ => unreachable!(),
         }
@@ -93,12 +119,87 @@ impl<'g> GameState<'g> for ArenaState<'g> {
         canvas: &mut Canvas<Window>,
         event_pump: &mut EventPump,
     ) {
-        println!("{:?}", self.game_mode);
+        // Campaign battles bring their own map, victory and rules
+        let battle = gi.campaign.as_ref().map(|run| run.battle.clone());
+        if let Some(battle) = &battle {
+            let team_sizes = self
+                .initial_bud_teams
+                .iter()
+                .map(|team| team.len() as u8)
+                .collect();
+            let game_mode = battle.victory.make_mode(team_sizes);
+            *self.game_mode.borrow_mut() = if battle.has_pickups() {
+                game_mode
+            } else {
+                game_mode.without_pickups()
+            };
+        }
+        // Scenarios fix everything up front, so nothing is left to chance
+        let scenario = gi.scenario.as_ref().map(|run| run.scenario.clone());
+        if let Some(scenario) = &scenario {
+            let team_sizes = self
+                .initial_bud_teams
+                .iter()
+                .map(|team| team.len() as u8)
+                .collect();
+            let game_mode = scenario.victory.make_mode(team_sizes).without_pickups();
+            *self.game_mode.borrow_mut() = match scenario.turns {
+                Some(turns) => game_mode.with_turn_limit(turns),
+                None => game_mode,
+            };
+        }
+        println!("{:?}", self.game_mode.borrow());
+        self.si
+            .events
+            .borrow_mut()
+            .subscribe(Rc::clone(&self.game_mode) as Rc<RefCell<dyn EventListener>>);
+        self.si
+            .events
+            .borrow_mut()
+            .subscribe(Rc::clone(&gi.match_info.combat_log) as Rc<RefCell<dyn EventListener>>);
+        gi.match_info
+            .combat_log
+            .borrow_mut()
+            .start_round(gi.match_info.round);
+        self.si
+            .events
+            .borrow_mut()
+            .subscribe(Rc::clone(&self.popups) as Rc<RefCell<dyn EventListener>>);
+        self.si
+            .gold_ledger
+            .borrow_mut()
+            .set_settings(gi.match_settings.clone());
+        self.si
+            .events
+            .borrow_mut()
+            .subscribe(Rc::clone(&self.si.gold_ledger) as Rc<RefCell<dyn EventListener>>);
+        self.si
+            .xp_ledger
+            .borrow_mut()
+            .set_settings(gi.match_settings.clone());
+        self.si
+            .events
+            .borrow_mut()
+            .subscribe(Rc::clone(&self.si.xp_ledger) as Rc<RefCell<dyn EventListener>>);
 
         const X_SIZE: u32 = 100;
         const Y_SIZE: u32 = 100;
 
-        let test_map = Map::new(vec!["rule_set_1.tmr", "rule_set_2.tmr"], "tile_map_2.tm");
+        let test_map = Map::new(
+            vec![
+                "rule_set_1.tmr",
+                "rule_set_2.tmr",
+                "rule_set_3.tmr",
+                "rule_set_4.tmr",
+                "rule_set_5.tmr",
+                "rule_set_6.tmr",
+            ],
+            scenario
+                .as_ref()
+                .map(|scenario| scenario.map.as_str())
+                .or(battle.as_ref().map(|battle| battle.map.as_str()))
+                .unwrap_or("tile_map_2.tm"),
+        );
         let tex = Rc::new(RefCell::new(
             gi.texture_creator
                 .load_texture(&"assets/tile_map/textures/bud_brawl_tiles.png")
@@ -123,67 +224,185 @@ impl<'g> GameState<'g> for ArenaState<'g> {
 
         let ground = Ground::new(Vector2d::new(0.0, 0.0), ground_tex);
         let _ground = Rc::new(RefCell::new(ground));
-        self.scene_manager.add(_ground);
+        self.scene_manager.add(self.si.registry.new_id(), _ground);
+
+        let mut terrain_textures = HashMap::new();
+        for terrain in Terrain::all() {
+            terrain_textures.insert(
+                terrain,
+                gi.texture_creator
+                    .load_texture(format!("assets/terrain/{}.png", terrain.get_texture_name()))
+                    .unwrap(),
+            );
+        }
+        for (tile, terrain) in test_map.terrain.iter() {
+            self.collisions.set_terrain(*tile, *terrain);
+        }
+        let terrain_layer = TerrainLayer::new(test_map.terrain.clone(), terrain_textures);
+        self.scene_manager.add(
+            self.si.registry.new_id(),
+            Rc::new(RefCell::new(terrain_layer)),
+        );
         for tile in tiles {
+            let id = self.si.registry.new_id();
             let tile = Rc::new(RefCell::new(tile));
             let _tile = Rc::clone(&tile);
             let __tile = Rc::clone(&tile);
-            self.collisions.add(__tile);
-            self.scene_manager.add(_tile);
+            self.collisions.add(id, __tile);
+            self.scene_manager.add(id, _tile);
         }
 
-        // let initial_bud_data = Rc::new(InitialBudData::default(tex.clone()));
+        self.pickups = PickupSpawner::new(
+            make_map(
+                "assets/icons",
+                &|file| Rc::new(gi.texture_creator.load_texture(file).unwrap()),
+                &["png", "jpg", "jpeg"],
+            ),
+            test_map.tiles.1,
+        );
+        let map_pickups =
+            scenario.is_none() && battle.as_ref().is_none_or(|battle| battle.has_pickups());
+        for (tile, kind) in test_map.pickups.iter().filter(|_| map_pickups) {
+            let id = self.si.registry.new_id();
+            let pickup = Rc::new(RefCell::new(self.pickups.make(*tile, *kind)));
+            let _pickup = Rc::clone(&pickup);
+            self.collisions.add(id, _pickup);
+            self.scene_manager.add(id, pickup);
+        }
 
-        // Team 0
-        for (i, initial_bud_data) in self.initial_buds_tuple.0.iter().enumerate() {
-            let mut bud = Bud::new(Point::new(0, i as i32), initial_bud_data.clone());
-            self.si.bud_data_tuple.0.push(Rc::clone(&bud.bud_data));
-            let _bud = Rc::new(RefCell::new(bud));
-            let __bud = Rc::clone(&_bud);
-            let ___bud = Rc::clone(&__bud);
+        // let initial_bud_data = Rc::new(InitialBudData::default(tex.clone()));
 
-            self.turn_handler.add(__bud);
-            self.scene_manager.add(_bud);
-            self.collisions.add(___bud);
-        }
+        // The arena spans the tile map and everywhere a bud starts
+        let (width, height) = test_map.tiles.1;
+        let mut arena = (
+            Point::new(0, 0),
+            Point::new(width as i32 - 1, height as i32 - 1),
+        );
 
-        // Team 1
-        for (i, initial_bud_data) in self.initial_buds_tuple.1.iter().enumerate() {
-            let mut bud = Bud::new(Point::new(10, i as i32), initial_bud_data.clone());
-            self.si.bud_data_tuple.1.push(Rc::clone(&bud.bud_data));
-            let _bud = Rc::new(RefCell::new(bud));
-            let __bud = Rc::clone(&_bud);
-            let ___bud = Rc::clone(&__bud);
+        let mut turn_order = Vec::new();
+        for (team, initial_buds) in self.initial_bud_teams.iter().enumerate() {
+            let mut bud_datas = Vec::new();
+            for (i, initial_bud_data) in initial_buds.iter().enumerate() {
+                let id = self.si.registry.new_id();
+                let placement = scenario
+                    .as_ref()
+                    .and_then(|scenario| scenario.get_placement(team as u8, i as u8));
+                let weight = initial_bud_data.get_weight();
+                let position = placement.map_or_else(
+                    || {
+                        // Never starts a bud on something solid, a blocked spot moves it further out
+                        (0..)
+                            .map(|back| {
+                                MatchSettings::get_spawn(team as u8, i as u8, back, (width, height))
+                            })
+                            .find(|spawn| self.collisions.can_enter(*spawn, weight))
+                            .unwrap()
+                    },
+                    |placement| placement.position,
+                );
+                arena.0 = Point::new(arena.0.x.min(position.x), arena.0.y.min(position.y));
+                arena.1 = Point::new(arena.1.x.max(position.x), arena.1.y.max(position.y));
+                let mut bud = Bud::new(
+                    id,
+                    position,
+                    initial_bud_data.clone(),
+                    Rc::clone(&self.si.events),
+                );
+                if let Some(placement) = placement {
+                    bud.face(placement.direction);
+                    if let Some(health) = placement.health {
+                        let mut bud_data = bud.bud_data.borrow_mut();
+                        bud_data.health = health.min(bud_data.initial.max_health);
+                    }
+                }
+                bud_datas.push(Rc::clone(&bud.bud_data));
+                let _bud = Rc::new(RefCell::new(bud));
+                let __bud = Rc::clone(&_bud);
+                let ___bud = Rc::clone(&__bud);
 
-            self.turn_handler.add(__bud);
-            self.scene_manager.add(_bud);
-            self.collisions.add(___bud);
+                let turn = scenario
+                    .as_ref()
+                    .and_then(|scenario| scenario.get_turn(team as u8, i as u8))
+                    .unwrap_or(0);
+                turn_order.push((turn, id, __bud));
+                self.scene_manager.add(id, _bud);
+                self.collisions.add(id, ___bud);
+            }
+            self.si.bud_data_teams.push(bud_datas);
+        }
+        turn_order.sort_by_key(|(turn, _, _)| *turn);
+        for (_, id, bud) in turn_order {
+            self.turn_handler.add(id, bud);
         }
 
-        self.initial_buds_tuple.0.drain(0..);
-        self.initial_buds_tuple.1.drain(0..);
+        // A scenario plays out as the file sets it up, in the listed turn order and without the
+        // arena closing in
+        if scenario.is_none() {
+            // Campaign battles can set their own round, everything else goes by the settings
+            let sudden_death_round = battle
+                .as_ref()
+                .and_then(|battle| battle.get_sudden_death_round())
+                .or(gi.match_settings.sudden_death);
+            if let Some(start_round) = sudden_death_round {
+                let sudden_death = Rc::new(RefCell::new(SuddenDeath::new(
+                    start_round,
+                    arena,
+                    gi.match_info.get_round_seed(),
+                )));
+                self.si
+                    .events
+                    .borrow_mut()
+                    .subscribe(Rc::clone(&sudden_death) as Rc<RefCell<dyn EventListener>>);
+                let _sudden_death = Rc::clone(&sudden_death);
+                self.scene_manager
+                    .add(self.si.registry.new_id(), _sudden_death);
+            }
+            self.turn_handler.sort_by_initiative();
+        }
+        self.initial_bud_teams.clear();
 
-        self.msh.add_menu_states(Box::new([(
-            MenuStateEnum::Bud(BudEnum::LeftBud(None)),
-            Box::new(BudState::new(gi)),
-        )]));
+        self.msh.add_menu_states(Box::new([
+            (
+                MenuStateEnum::Bud(BudEnum::LeftBud(None)),
+                Box::new(BudState::new(gi)),
+            ),
+            (
+                MenuStateEnum::CombatLog,
+                Box::new(CombatLogState::new(
+                    gi,
+                    Rc::clone(&gi.match_info.combat_log),
+                )),
+            ),
+        ]));
     }
     fn run(&mut self, gi: &mut GameInfo<'g>, delta_time: f32, canvas: &mut Canvas<Window>) {
         //Handling the ending of a turn!
-        if self.end_turn && gi.input.is_pressed(Keycode::Return) {
+        let player_ends_turn =
+            !self.si.computer_turn && self.end_turn && gi.input.is_pressed(Keycode::Return);
+        if player_ends_turn || std::mem::take(&mut self.si.end_turn) {
             self.turn_handler.next_turn(
                 delta_time,
                 &mut self.collisions,
                 gi,
                 &mut self.si,
                 &mut self.msh,
-                &mut self.game_mode,
             );
             println!("?");
             self.end_turn = false;
         } else if gi.input.is_released(Keycode::Return) {
             self.end_turn = true;
         }
+        if self.toggle_log && gi.input.is_pressed(Keycode::L) {
+            if self.msh.state == Some(MenuStateEnum::CombatLog) {
+                self.msh
+                    .load_menu(MenuStateEnum::Bud(BudEnum::LeftBud(None)));
+            } else {
+                self.msh.load_menu(MenuStateEnum::CombatLog);
+            }
+            self.toggle_log = false;
+        } else if gi.input.is_released(Keycode::L) {
+            self.toggle_log = true;
+        }
         let mouse_state = gi.input.mouse_state.clone();
         self.view.move_view(delta_time, gi);
         self.button.press(&mouse_state, gi, None);
@@ -191,6 +410,21 @@ impl<'g> GameState<'g> for ArenaState<'g> {
 
         self.scene_manager
             .update(delta_time, &mut self.collisions, gi, &mut self.si);
+        self.si.events.borrow_mut().dispatch();
+        self.popups
+            .borrow_mut()
+            .spawn(&self.collisions, &mut self.si);
+        self.si.remove_objects(
+            &mut self.scene_manager,
+            &mut self.collisions,
+            &mut self.turn_handler,
+        );
+        self.game_mode
+            .borrow_mut()
+            .check_done(&mut self.collisions, gi, &mut self.si);
+        self.game_mode
+            .borrow_mut()
+            .spawn_pickups(&self.collisions, &mut self.si, &self.pickups);
         self.scene_manager.draw(canvas, &mut gi.camera);
         self.scene_manager.press(gi, &mut self.msh);
         self.msh.handle_state(gi, delta_time, canvas);
@@ -215,12 +449,13 @@ impl<'g> GameState<'g> for ArenaState<'g> {
             gi.camera.window_scale() as u32,
             gi.camera.window_scale() as u32,
         ));
-        self.si.add_objects(&mut self.scene_manager);
+        self.si
+            .add_objects(&mut self.scene_manager, &mut self.collisions);
 
         canvas.string(0, 0, "Arena", sdl2::pixels::Color::RGB(0, 255, 0));
 
         if gi.input.is_pressed(Keycode::R) {
-            self.si.end_round(gi);
+            self.si.end_round(gi, &[]);
         }
     }
 }
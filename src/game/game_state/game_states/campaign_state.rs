@@ -0,0 +1,280 @@
+use std::{collections::HashMap, rc::Rc};
+
+use sdl2::{
+    gfx::primitives::DrawRenderer,
+    image::LoadTexture,
+    pixels::Color,
+    rect::{Point, Rect},
+    render::{Canvas, Texture},
+    video::Window,
+    EventPump,
+};
+
+use crate::game::{
+    button::{Button, MenuButton},
+    campaign::{Campaign, CampaignProgress, CampaignRun},
+    game_info::{make_map, GameInfo},
+    game_object::game_objects::bud::{bud_data::InitialBudData, weapon::WeaponInfo},
+    game_state::{
+        game_states::{select_state::NameGenerator, GameStateEnum},
+        GameState,
+    },
+    match_settings::MatchSettings,
+};
+
+/// Where the row of battles is drawn
+const MAP_Y: i32 = 30;
+const MAP_LEFT: i32 = 10;
+const MAP_WIDTH: i32 = 80;
+
+#[derive(Clone, Copy, PartialEq)]
+enum CampaignCommand {
+    Fight,
+    Reset,
+    Home,
+}
+
+/// The campaign map, shows the battles won and the next one, and starts it
+pub struct CampaignState<'g> {
+    campaign: Campaign,
+    progress: CampaignProgress,
+    buttons: Vec<MenuButton<Option<CampaignCommand>>>,
+    command: Option<CampaignCommand>,
+    icons: HashMap<String, Rc<Texture<'g>>>,
+    bud_texture: Option<Rc<Texture<'g>>>,
+    enemy_texture: Option<Rc<Texture<'g>>>,
+}
+
+impl<'g> CampaignState<'g> {
+    pub fn new() -> Self {
+        let campaign = Campaign::load();
+        let progress = CampaignProgress::load(&campaign);
+        let buttons = [
+            (10, "Fight", CampaignCommand::Fight),
+            (35, "Reset", CampaignCommand::Reset),
+            (60, "Home", CampaignCommand::Home),
+        ]
+        .into_iter()
+        .map(|(x, text, command)| {
+            MenuButton::new(
+                Rect::new(x, 85, 20, 10),
+                text,
+                Box::new(move |pressed: &mut Option<CampaignCommand>| {
+                    *pressed = Some(command);
+                }),
+            )
+        })
+        .collect();
+        Self {
+            campaign,
+            progress,
+            buttons,
+            command: None,
+            icons: HashMap::new(),
+            bud_texture: None,
+            enemy_texture: None,
+        }
+    }
+    pub fn new_state(state: &GameStateEnum<'g>) -> Box<dyn GameState<'g> + 'g> {
+        match state {
+            GameStateEnum::Campaign => Box::new(Self::new()),
+            _ => unreachable!(),
+        }
+    }
+    /// Sets the match up for the next battle and heads to the select screen with both teams
+    fn fight(&mut self, gi: &mut GameInfo<'g>) {
+        let index = self.progress.completed;
+        let (Some(battle), Some(bud_texture), Some(enemy_texture)) = (
+            self.campaign.battles.get(index),
+            &self.bud_texture,
+            &self.enemy_texture,
+        ) else {
+            return;
+        };
+        let name_generator = NameGenerator::new("assets/names/names.txt");
+        let mut player_buds: Vec<InitialBudData<'g>> = Vec::new();
+        for bud in self.progress.roster.iter() {
+            let bud_index = player_buds.len() as u8;
+            player_buds.extend(bud.to_initial(0, bud_index, Rc::clone(bud_texture), &self.icons));
+        }
+        if let Some(sword_tex) = self.icons.get("sword") {
+            while player_buds.len() < self.campaign.squad as usize {
+                player_buds.push(InitialBudData::default(
+                    Rc::clone(bud_texture),
+                    0,
+                    player_buds.len() as u8,
+                    &name_generator,
+                    WeaponInfo::default(Rc::clone(sword_tex)),
+                ));
+            }
+        } else {
+            println!("Not filling the squad, there is no icon for the sword");
+        }
+        let mut enemy_buds: Vec<InitialBudData<'g>> = Vec::new();
+        for bud in battle.enemies.iter() {
+            let bud_index = enemy_buds.len() as u8;
+            enemy_buds.extend(bud.to_initial(1, bud_index, Rc::clone(enemy_texture), &self.icons));
+        }
+        if player_buds.is_empty() || enemy_buds.is_empty() {
+            println!("Battle {} has no one to fight it", index + 1);
+            return;
+        }
+
+        let previous_settings = gi.match_settings.clone();
+        let mut settings = MatchSettings::new();
+        settings.set_team_size(player_buds.len().max(enemy_buds.len()) as u8);
+        settings.computer = Some(battle.difficulty);
+        gi.match_settings = settings;
+        gi.match_info.reset(gi.match_settings.team_count);
+        gi.campaign = Some(CampaignRun::new(
+            index,
+            battle.clone(),
+            self.progress.unlocked_traits.clone(),
+            previous_settings,
+        ));
+        gi.game_state_handler
+            .new_state(GameStateEnum::Select(vec![player_buds, enemy_buds]));
+    }
+    /// Logs a texture that fails to load and leaves it out, `fight` won't start a battle without it
+    fn load_bud_texture(gi: &GameInfo<'g>, file: &str) -> Option<Rc<Texture<'g>>> {
+        gi.texture_creator
+            .load_texture(file)
+            .map_err(|e| println!("Could not load {file}: {e}"))
+            .ok()
+            .map(Rc::new)
+    }
+    fn draw_text(&self, gi: &GameInfo, canvas: &mut Canvas<Window>, x: i32, y: i32, text: &str) {
+        let mut point = Point::new(x, y);
+        gi.camera.ui_point_to_camera(&mut point);
+        let _ = canvas.string(point.x as i16, point.y as i16, text, Color::RGB(0, 0, 0));
+    }
+}
+
+impl<'g> GameState<'g> for CampaignState<'g> {
+    fn start(
+        &mut self,
+        gi: &mut GameInfo<'g>,
+        _delta_time: f32,
+        _canvas: &mut Canvas<Window>,
+        _event_pump: &mut EventPump,
+    ) {
+        self.icons = make_map(
+            "assets/icons",
+            &|file| match gi.texture_creator.load_texture(file) {
+                Ok(texture) => Some(Rc::new(texture)),
+                Err(e) => {
+                    println!("Skipping icon {file}: {e}");
+                    None
+                }
+            },
+            &["png", "jpg", "jpeg"],
+        )
+        .into_iter()
+        .filter_map(|(name, icon)| Some((name, icon?)))
+        .collect();
+        self.bud_texture = Self::load_bud_texture(gi, "assets/bud_2.png");
+        self.enemy_texture = Self::load_bud_texture(gi, "assets/bud_1.png");
+    }
+
+    fn run(&mut self, gi: &mut GameInfo<'g>, _delta_time: f32, canvas: &mut Canvas<Window>) {
+        match self.command.take() {
+            Some(CampaignCommand::Fight) => self.fight(gi),
+            Some(CampaignCommand::Reset) => {
+                self.progress = CampaignProgress::new(&self.campaign);
+                self.progress.save();
+            }
+            Some(CampaignCommand::Home) => {
+                gi.game_state_handler
+                    .new_state(GameStateEnum::Home(Color::RGB(0, 0, 255)));
+            }
+            None => {}
+        }
+
+        self.draw_text(gi, canvas, MAP_LEFT, 10, "Campaign");
+
+        // The battles in a row, won ones in green and the next one in yellow
+        let count = self.campaign.battles.len();
+        let step = MAP_WIDTH / (count as i32 - 1).max(1);
+        for (i, battle) in self.campaign.battles.iter().enumerate() {
+            let mut center = Point::new(MAP_LEFT + step * i as i32, MAP_Y);
+            gi.camera.ui_point_to_camera(&mut center);
+            if i + 1 < count {
+                let mut next = Point::new(MAP_LEFT + step * (i as i32 + 1), MAP_Y);
+                gi.camera.ui_point_to_camera(&mut next);
+                canvas.set_draw_color(Color::RGB(0, 0, 0));
+                let _ = canvas.draw_line(center, next);
+            }
+            let color = if i < self.progress.completed {
+                Color::RGB(60, 180, 80)
+            } else if i == self.progress.completed {
+                Color::RGB(230, 200, 50)
+            } else {
+                Color::RGB(150, 150, 150)
+            };
+            let mut size = Rect::new(0, 0, 3, 3);
+            gi.camera.ui_rect_to_camera(&mut size);
+            let radius = size.width() as i16;
+            let _ = canvas.filled_circle(center.x as i16, center.y as i16, radius, color);
+            let _ = canvas.circle(
+                center.x as i16,
+                center.y as i16,
+                radius,
+                Color::RGB(0, 0, 0),
+            );
+            // Every other name sits lower so long names don't run into each other
+            let y = MAP_Y + 5 + 3 * (i as i32 % 2);
+            self.draw_text(gi, canvas, MAP_LEFT + step * i as i32 - 3, y, &battle.name);
+        }
+
+        let mut lines = Vec::new();
+        match self.campaign.battles.get(self.progress.completed) {
+            Some(battle) => {
+                lines.push(format!(
+                    "Battle {}: {}",
+                    self.progress.completed + 1,
+                    battle.name
+                ));
+                lines.push(battle.description.clone());
+                lines.push(format!("Victory: {}", battle.victory.get_description()));
+                lines.push(format!(
+                    "{} enemies, {} difficulty",
+                    battle.enemies.len(),
+                    battle.difficulty.get_name()
+                ));
+                lines.extend(battle.rules.iter().map(|rule| rule.get_description()));
+                if !battle.unlocks.is_empty() {
+                    lines.push(format!("Unlocks: {}", battle.unlocks.join(", ")));
+                }
+            }
+            None => lines.push(String::from("Campaign complete!")),
+        }
+        lines.push(String::new());
+        let roster: Vec<String> = self
+            .progress
+            .roster
+            .iter()
+            .map(|bud| format!("{} (Level {})", bud.name, bud.level))
+            .collect();
+        lines.push(format!(
+            "Veterans: {}, topped up to {} buds with recruits",
+            if roster.is_empty() {
+                String::from("none")
+            } else {
+                roster.join(", ")
+            },
+            self.campaign.squad
+        ));
+        lines.push(format!(
+            "Traits: {}",
+            self.progress.unlocked_traits.join(", ")
+        ));
+        for (i, line) in lines.iter().enumerate() {
+            self.draw_text(gi, canvas, MAP_LEFT, 45 + 3 * i as i32, line);
+        }
+
+        for button in self.buttons.iter_mut() {
+            button.press(&gi.input.mouse_state, &mut self.command, Some(&gi.camera));
+            button.draw(canvas, &gi.camera);
+        }
+    }
+}
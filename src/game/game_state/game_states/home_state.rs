@@ -1,7 +1,11 @@
 use std::{cell::RefCell, rc::Rc};
 
 use sdl2::{
-    gfx::primitives::DrawRenderer, keyboard::Keycode, rect::Rect, render::Canvas, video::Window,
+    gfx::primitives::DrawRenderer,
+    keyboard::Keycode,
+    rect::{Point, Rect},
+    render::Canvas,
+    video::Window,
     EventPump,
 };
 
@@ -10,6 +14,7 @@ use crate::game::{
     camera,
     game_info::GameInfo,
     game_state::{game_states::GameStateEnum, GameState},
+    match_settings::TeamMode,
 };
 
 pub struct HomeState<'h> {
@@ -25,8 +30,91 @@ impl<'g> HomeState<'g> {
             Rect::new(10, 10, 10, 20),
             "Start",
             Box::new(|gi: &mut GameInfo| {
+                gi.match_info.reset(gi.match_settings.team_count);
                 gi.game_state_handler
-                    .new_state(GameStateEnum::Select((Vec::new(), Vec::new())));
+                    .new_state(GameStateEnum::Select(Vec::new()));
+            }),
+        ));
+        buttons.push(MenuButton::new(
+            Rect::new(10, 35, 10, 10),
+            "Campaign",
+            Box::new(|gi: &mut GameInfo| {
+                gi.game_state_handler.new_state(GameStateEnum::Campaign);
+            }),
+        ));
+        buttons.push(MenuButton::new(
+            Rect::new(10, 50, 10, 10),
+            "Scenarios",
+            Box::new(|gi: &mut GameInfo| {
+                gi.game_state_handler.new_state(GameStateEnum::Scenarios);
+            }),
+        ));
+        for (i, (text, team_count)) in [("2 Teams", 2), ("3 Teams", 3), ("4 Teams", 4)]
+            .into_iter()
+            .enumerate()
+        {
+            buttons.push(MenuButton::new(
+                Rect::new(25, 10 + 6 * i as i32, 12, 5),
+                text,
+                Box::new(move |gi: &mut GameInfo| {
+                    gi.match_settings.set_team_count(team_count);
+                }),
+            ));
+        }
+        for (i, mode) in [TeamMode::FreeForAll, TeamMode::TeamVsTeam]
+            .into_iter()
+            .enumerate()
+        {
+            buttons.push(MenuButton::new(
+                Rect::new(40, 10 + 6 * i as i32, 16, 5),
+                mode.get_name(),
+                Box::new(move |gi: &mut GameInfo| {
+                    gi.match_settings.mode = mode;
+                }),
+            ));
+        }
+        buttons.push(MenuButton::new(
+            Rect::new(60, 10, 12, 5),
+            "Fewer Buds",
+            Box::new(|gi: &mut GameInfo| {
+                let team_size = gi.match_settings.team_size;
+                gi.match_settings.set_team_size(team_size.saturating_sub(1));
+            }),
+        ));
+        buttons.push(MenuButton::new(
+            Rect::new(60, 16, 12, 5),
+            "More Buds",
+            Box::new(|gi: &mut GameInfo| {
+                let team_size = gi.match_settings.team_size;
+                gi.match_settings.set_team_size(team_size + 1);
+            }),
+        ));
+        buttons.push(MenuButton::new(
+            Rect::new(60, 22, 12, 5),
+            "Budget",
+            Box::new(|gi: &mut GameInfo| {
+                gi.match_settings.use_budget = !gi.match_settings.use_budget;
+            }),
+        ));
+        buttons.push(MenuButton::new(
+            Rect::new(60, 28, 12, 5),
+            "Tournament",
+            Box::new(|gi: &mut GameInfo| {
+                gi.match_settings.next_rounds();
+            }),
+        ));
+        buttons.push(MenuButton::new(
+            Rect::new(60, 34, 12, 5),
+            "Draft",
+            Box::new(|gi: &mut GameInfo| {
+                gi.match_settings.draft = !gi.match_settings.draft;
+            }),
+        ));
+        buttons.push(MenuButton::new(
+            Rect::new(60, 40, 12, 5),
+            "Sudden Death",
+            Box::new(|gi: &mut GameInfo| {
+                gi.match_settings.next_sudden_death();
             }),
         ));
         // buttons.push(MenuButton::new(
@@ -68,5 +156,53 @@ impl<'g> GameState<'g> for HomeState<'g> {
         canvas.set_draw_color(self.color);
         canvas.draw_rect(Rect::new(10, 10, 100, 100));
         canvas.string(0, 0, "Home", self.color);
+
+        let settings = &gi.match_settings;
+        let mut point = Point::new(25, 30);
+        gi.camera.ui_point_to_camera(&mut point);
+        let _ = canvas.string(
+            point.x as i16,
+            point.y as i16,
+            &format!(
+                "{} Teams, {}",
+                settings.team_count,
+                settings.mode.get_name()
+            ),
+            self.color,
+        );
+        let budget = match settings.get_budget() {
+            Some(budget) => format!("{budget} points per team"),
+            None => String::from("no points budget"),
+        };
+        let mut point = Point::new(25, 33);
+        gi.camera.ui_point_to_camera(&mut point);
+        let _ = canvas.string(
+            point.x as i16,
+            point.y as i16,
+            &format!("{} Buds per team, {}", settings.team_size, budget),
+            self.color,
+        );
+        let tournament = match settings.rounds {
+            Some(rounds) => format!("Tournament, best of {rounds} rounds"),
+            None => String::from("No tournament, rounds go on until you quit"),
+        };
+        let mut point = Point::new(25, 36);
+        gi.camera.ui_point_to_camera(&mut point);
+        let _ = canvas.string(point.x as i16, point.y as i16, &tournament, self.color);
+        let picking = if settings.draft {
+            "Teams are drafted from a shared pool"
+        } else {
+            "Teams pick their own buds"
+        };
+        let mut point = Point::new(25, 39);
+        gi.camera.ui_point_to_camera(&mut point);
+        let _ = canvas.string(point.x as i16, point.y as i16, picking, self.color);
+        let sudden_death = match settings.sudden_death {
+            Some(round) => format!("The arena closes in after {round} rounds"),
+            None => String::from("No sudden death"),
+        };
+        let mut point = Point::new(25, 42);
+        gi.camera.ui_point_to_camera(&mut point);
+        let _ = canvas.string(point.x as i16, point.y as i16, &sudden_death, self.color);
     }
 }
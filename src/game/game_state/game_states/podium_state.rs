@@ -0,0 +1,141 @@
+use std::rc::Rc;
+
+use sdl2::{
+    gfx::primitives::DrawRenderer,
+    image::LoadTexture,
+    pixels::Color,
+    rect::{Point, Rect},
+    render::{Canvas, Texture},
+    video::Window,
+    EventPump,
+};
+
+use crate::game::{
+    button::{Button, MenuButton},
+    game_info::GameInfo,
+    game_object::game_objects::bud::bud_data::InitialBudData,
+    game_state::{game_states::GameStateEnum, GameState},
+    match_info::BudRecord,
+    match_settings::MatchSettings,
+};
+
+/// Heights of the podium steps, from first place down
+const STEP_HEIGHTS: [i32; 3] = [30, 20, 12];
+/// Where each step stands, first place in the middle
+const STEP_X: [i32; 3] = [40, 20, 60];
+const STEP_WIDTH: u32 = 20;
+/// Where the steps stand on
+const FLOOR: i32 = 75;
+
+/// Closes out a tournament with the top teams and the match's most valuable bud
+pub struct PodiumState<'g> {
+    initial_bud_teams: Vec<Vec<InitialBudData<'g>>>,
+    mvp: Option<BudRecord>,
+    first_place: Option<Rc<Texture<'g>>>,
+    button: MenuButton<GameInfo<'g>>,
+}
+
+impl<'g> PodiumState<'g> {
+    pub fn new(initial_bud_teams: Vec<Vec<InitialBudData<'g>>>) -> Self {
+        Self {
+            initial_bud_teams,
+            mvp: None,
+            first_place: None,
+            button: MenuButton::new(
+                Rect::new(40, 85, 20, 10),
+                "Home",
+                Box::new(|gi: &mut GameInfo| {
+                    gi.game_state_handler
+                        .new_state(GameStateEnum::Home(Color::RGB(0, 0, 255)));
+                }),
+            ),
+        }
+    }
+    pub fn new_state(state: &GameStateEnum<'g>) -> Box<dyn GameState<'g> + 'g> {
+        match state {
+            GameStateEnum::Podium(initial_bud_teams) => {
+                Box::new(Self::new(initial_bud_teams.clone()))
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'g> GameState<'g> for PodiumState<'g> {
+    fn start(
+        &mut self,
+        gi: &mut GameInfo<'g>,
+        _delta_time: f32,
+        _canvas: &mut Canvas<Window>,
+        _event_pump: &mut EventPump,
+    ) {
+        self.mvp = gi.match_info.get_mvp(&self.initial_bud_teams);
+        self.first_place = gi
+            .texture_creator
+            .load_texture("assets/icons/first_place.png")
+            .ok()
+            .map(Rc::new);
+    }
+
+    fn run(&mut self, gi: &mut GameInfo<'g>, _delta_time: f32, canvas: &mut Canvas<Window>) {
+        for (place, team) in gi
+            .match_info
+            .get_standings(&self.initial_bud_teams)
+            .into_iter()
+            .take(3)
+            .enumerate()
+        {
+            let height = STEP_HEIGHTS[place];
+            let mut rect = Rect::new(STEP_X[place], FLOOR - height, STEP_WIDTH, height as u32);
+            gi.camera.ui_rect_to_camera(&mut rect);
+            canvas.set_draw_color(MatchSettings::get_team_color(team));
+            let _ = canvas.fill_rect(rect);
+            canvas.set_draw_color(Color::RGB(0, 0, 0));
+            let _ = canvas.draw_rect(rect);
+
+            let mut point = Point::new(STEP_X[place] + 1, FLOOR - height - 5);
+            gi.camera.ui_point_to_camera(&mut point);
+            let _ = canvas.string(
+                point.x as i16,
+                point.y as i16,
+                &format!("Team {}", team + 1),
+                Color::RGB(0, 0, 0),
+            );
+            let mut point = Point::new(STEP_X[place] + 1, FLOOR - height - 3);
+            gi.camera.ui_point_to_camera(&mut point);
+            let _ = canvas.string(
+                point.x as i16,
+                point.y as i16,
+                &format!("{} wins", gi.match_info.get_wins(team)),
+                Color::RGB(0, 0, 0),
+            );
+        }
+
+        if let Some(mvp) = &self.mvp {
+            if let Some(first_place) = &self.first_place {
+                let mut rect = Rect::new(30, 8, 5, 5);
+                gi.camera.ui_rect_to_camera(&mut rect);
+                let _ = canvas.copy_ex(first_place, None, rect, 0.0, None, false, false);
+            }
+            let mut point = Point::new(36, 10);
+            gi.camera.ui_point_to_camera(&mut point);
+            let _ = canvas.string(
+                point.x as i16,
+                point.y as i16,
+                &format!(
+                    "MVP: {} of Team {}, {} kills, {} damage",
+                    mvp.name,
+                    mvp.team + 1,
+                    mvp.kills,
+                    mvp.damage
+                ),
+                MatchSettings::get_team_color(mvp.team),
+            );
+        }
+
+        let mouse_state = gi.input.mouse_state;
+        let camera = gi.camera.clone();
+        self.button.press(&mouse_state, gi, Some(&camera));
+        self.button.draw(canvas, &gi.camera);
+    }
+}
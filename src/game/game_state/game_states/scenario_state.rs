@@ -0,0 +1,201 @@
+use std::{collections::HashMap, rc::Rc};
+
+use sdl2::{
+    gfx::primitives::DrawRenderer,
+    image::LoadTexture,
+    pixels::Color,
+    rect::{Point, Rect},
+    render::{Canvas, Texture},
+    video::Window,
+    EventPump,
+};
+
+use crate::game::{
+    button::{Button, MenuButton},
+    game_info::{make_map, GameInfo},
+    game_state::{game_states::GameStateEnum, GameState},
+    scenario::{Scenario, ScenarioProgress, ScenarioRun},
+};
+
+const LIST_LEFT: i32 = 10;
+const LIST_TOP: i32 = 20;
+
+#[derive(Clone, Copy, PartialEq)]
+enum ScenarioCommand {
+    Previous,
+    Next,
+    Play,
+    Home,
+}
+
+/// Lists the scenarios with the solved ones checked off, and starts the one picked
+pub struct ScenarioState<'g> {
+    scenarios: Vec<Scenario>,
+    progress: ScenarioProgress,
+    selected: usize,
+    buttons: Vec<MenuButton<Option<ScenarioCommand>>>,
+    command: Option<ScenarioCommand>,
+    icons: HashMap<String, Rc<Texture<'g>>>,
+    bud_texture: Option<Rc<Texture<'g>>>,
+    enemy_texture: Option<Rc<Texture<'g>>>,
+}
+
+impl<'g> ScenarioState<'g> {
+    pub fn new() -> Self {
+        let buttons = [
+            (10, "Previous", ScenarioCommand::Previous),
+            (30, "Next", ScenarioCommand::Next),
+            (50, "Play", ScenarioCommand::Play),
+            (70, "Home", ScenarioCommand::Home),
+        ]
+        .into_iter()
+        .map(|(x, text, command)| {
+            MenuButton::new(
+                Rect::new(x, 85, 15, 10),
+                text,
+                Box::new(move |pressed: &mut Option<ScenarioCommand>| {
+                    *pressed = Some(command);
+                }),
+            )
+        })
+        .collect();
+        Self {
+            scenarios: Scenario::load_all(),
+            progress: ScenarioProgress::load(),
+            selected: 0,
+            buttons,
+            command: None,
+            icons: HashMap::new(),
+            bud_texture: None,
+            enemy_texture: None,
+        }
+    }
+    pub fn new_state(state: &GameStateEnum<'g>) -> Box<dyn GameState<'g> + 'g> {
+        match state {
+            GameStateEnum::Scenarios => Box::new(Self::new()),
+            _ => unreachable!(),
+        }
+    }
+    /// Puts the picked scenario's buds down in the arena, skipping selection
+    fn play(&mut self, gi: &mut GameInfo<'g>) {
+        let (Some(scenario), Some(bud_texture), Some(enemy_texture)) = (
+            self.scenarios.get(self.selected),
+            &self.bud_texture,
+            &self.enemy_texture,
+        ) else {
+            return;
+        };
+        if scenario.get_team_count() < 2 {
+            println!("Scenario {} needs at least two teams", scenario.file);
+            return;
+        }
+        let teams = scenario.make_teams(bud_texture, enemy_texture, &self.icons);
+        let previous_settings = std::mem::replace(&mut gi.match_settings, scenario.make_settings());
+        gi.match_info.reset(gi.match_settings.team_count);
+        gi.scenario = Some(ScenarioRun::new(scenario.clone(), previous_settings));
+        gi.game_state_handler.new_state(GameStateEnum::Arena(teams));
+    }
+    fn draw_text(&self, gi: &GameInfo, canvas: &mut Canvas<Window>, x: i32, y: i32, text: &str) {
+        let mut point = Point::new(x, y);
+        gi.camera.ui_point_to_camera(&mut point);
+        let _ = canvas.string(point.x as i16, point.y as i16, text, Color::RGB(0, 0, 0));
+    }
+}
+
+impl<'g> GameState<'g> for ScenarioState<'g> {
+    fn start(
+        &mut self,
+        gi: &mut GameInfo<'g>,
+        _delta_time: f32,
+        _canvas: &mut Canvas<Window>,
+        _event_pump: &mut EventPump,
+    ) {
+        self.icons = make_map(
+            "assets/icons",
+            &|file| Rc::new(gi.texture_creator.load_texture(file).unwrap()),
+            &["png", "jpg", "jpeg"],
+        );
+        self.bud_texture = Some(Rc::new(
+            gi.texture_creator.load_texture("assets/bud_2.png").unwrap(),
+        ));
+        self.enemy_texture = Some(Rc::new(
+            gi.texture_creator.load_texture("assets/bud_1.png").unwrap(),
+        ));
+    }
+
+    fn run(&mut self, gi: &mut GameInfo<'g>, _delta_time: f32, canvas: &mut Canvas<Window>) {
+        let count = self.scenarios.len().max(1);
+        match self.command.take() {
+            Some(ScenarioCommand::Previous) => self.selected = (self.selected + count - 1) % count,
+            Some(ScenarioCommand::Next) => self.selected = (self.selected + 1) % count,
+            Some(ScenarioCommand::Play) => self.play(gi),
+            Some(ScenarioCommand::Home) => {
+                gi.game_state_handler
+                    .new_state(GameStateEnum::Home(Color::RGB(0, 0, 255)));
+            }
+            None => {}
+        }
+
+        self.draw_text(
+            gi,
+            canvas,
+            LIST_LEFT,
+            10,
+            &format!(
+                "Scenarios, {} of {} solved",
+                self.scenarios
+                    .iter()
+                    .filter(|scenario| self.progress.is_completed(&scenario.file))
+                    .count(),
+                self.scenarios.len()
+            ),
+        );
+        if self.scenarios.is_empty() {
+            self.draw_text(gi, canvas, LIST_LEFT, LIST_TOP, "No scenarios found");
+        }
+        for (i, scenario) in self.scenarios.iter().enumerate() {
+            let check = if self.progress.is_completed(&scenario.file) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            let cursor = if i == self.selected { ">" } else { " " };
+            self.draw_text(
+                gi,
+                canvas,
+                LIST_LEFT,
+                LIST_TOP + 4 * i as i32,
+                &format!("{cursor} {check} {}", scenario.name),
+            );
+        }
+
+        // The picked scenario's details, next to the list
+        if let Some(scenario) = self.scenarios.get(self.selected) {
+            let mut lines = vec![
+                scenario.name.clone(),
+                scenario.description.clone(),
+                format!("Victory: {}", scenario.victory.get_description()),
+            ];
+            lines.push(match scenario.turns {
+                Some(1) => String::from("Win in 1 turn"),
+                Some(turns) => format!("Win within {turns} turns"),
+                None => String::from("No turn limit"),
+            });
+            let teams = scenario.get_teams();
+            lines.push(format!(
+                "{} buds against {}, {} difficulty",
+                teams.first().map_or(0, Vec::len),
+                teams.iter().skip(1).map(Vec::len).sum::<usize>(),
+                scenario.difficulty.get_name()
+            ));
+            for (i, line) in lines.iter().enumerate() {
+                self.draw_text(gi, canvas, 50, LIST_TOP + 4 * i as i32, line);
+            }
+        }
+
+        for button in self.buttons.iter_mut() {
+            button.press(&gi.input.mouse_state, &mut self.command, Some(&gi.camera));
+            button.draw(canvas, &gi.camera);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use rand::seq::IndexedRandom;
+use rand::{seq::IndexedRandom, Rng};
 use std::{
     cell::RefCell,
     collections::HashMap,
@@ -20,51 +20,224 @@ use sdl2::{
 
 use crate::game::{
     button::{Button, MenuButton},
+    effect_system::effects::{
+        self_effect::{BulwarkEffect, FighterEffect, MendingEffect, ScoutEffect},
+        Effect,
+    },
     game_info::{make_map, GameInfo},
     game_object::game_objects::bud::{
         bud_data::InitialBudData,
         weapon::{WeaponEnum, WeaponInfo},
     },
     game_state::{game_states::GameStateEnum, GameState},
+    match_info::MatchInfo,
     menu::menu_state::menu_states::{
-        select_bud_state::SelectBudState, MenuStateEnum, MenuStateHandler,
+        draft_state::DraftState, select_bud_state::SelectBudState, MenuStateEnum, MenuStateHandler,
     },
 };
 
+/// Gold to recruit a bud into a team that lost some
+pub const RECRUIT_COST: u32 = 4;
+/// Gold for a weapon's first upgrade, each further one costs this much more
+const UPGRADE_COST: u32 = 3;
+/// Gold for one more trait slot
+pub const TRAIT_SLOT_COST: u32 = 3;
+/// Buds in the draft pool beyond what fills every team, per team
+const DRAFT_EXTRA: usize = 1;
+
 pub struct SelectState<'g> {
     buttons: Vec<MenuButton<GameInfo<'g>>>,
     select_info: Rc<RefCell<SelectInfo<'g>>>,
     msh: MenuStateHandler<'g>,
     weapon_released: bool,
+    drafting: bool,
 }
 
 pub struct SelectInfo<'g> {
-    pub initial_buds_tuple: (Vec<InitialBudData<'g>>, Vec<InitialBudData<'g>>),
+    /// The buds of every team, indexed by team
+    pub initial_bud_teams: Vec<Vec<InitialBudData<'g>>>,
     pub current_bud: Option<usize>,
     pub trait_description: String,
     pub team: u8,
     pub done: bool,
     pub icon_textures: HashMap<String, Rc<Texture<'g>>>,
     pub weapon_index: u8,
+    /// Points each team can spend, if the match has a budget
+    pub budget: Option<u16>,
+    /// Gold is spent from here and handed back to the match once picking is done
+    pub match_info: MatchInfo,
+    pub team_size: usize,
+    /// Teams picked here, the computer's teams come after them and are left as they are
+    pub player_teams: u8,
+    /// Lowercase names of the traits that can be picked, `None` when all of them can
+    pub unlocked_traits: Option<Vec<String>>,
+    /// What a recruit is made from, set once the state starts
+    pub bud_texture: Option<Rc<Texture<'g>>>,
+    pub name_generator: Option<NameGenerator>,
+    /// Pre-rolled buds left to be drafted, empty outside of a draft
+    pub draft_pool: Vec<InitialBudData<'g>>,
+    /// Picks made so far in the draft
+    pub draft_pick: usize,
 }
 
 impl<'g> SelectInfo<'g> {
     pub fn get_current_initial_bud_data(&mut self) -> Option<&mut InitialBudData<'g>> {
         if let Some(current_bud) = self.current_bud {
-            let initial_buds_tuple = if self.team == 0 {
-                &mut self.initial_buds_tuple.0
-            } else {
-                &mut self.initial_buds_tuple.1
-            };
-            return Some(&mut initial_buds_tuple[current_bud]);
+            Some(&mut self.initial_bud_teams[self.team as usize][current_bud])
         } else {
             None
         }
     }
+    /// Points the team being picked has spent
+    pub fn get_team_cost(&self) -> u16 {
+        self.initial_bud_teams[self.team as usize]
+            .iter()
+            .map(|initial_bud_data| initial_bud_data.get_cost())
+            .sum()
+    }
+    pub fn within_budget(&self) -> bool {
+        self.budget
+            .is_none_or(|budget| self.get_team_cost() <= budget)
+    }
+    pub fn is_unlocked(&self, name: &str) -> bool {
+        let name = name.to_lowercase().replace(' ', "_");
+        self.unlocked_traits
+            .as_ref()
+            .is_none_or(|unlocked| unlocked.contains(&name))
+    }
+    pub fn get_gold(&self) -> u32 {
+        self.match_info.get_gold(self.team)
+    }
+    pub fn get_upgrade_cost(&mut self) -> Option<u32> {
+        self.get_current_initial_bud_data()
+            .map(|initial| UPGRADE_COST * (initial.weapon_info.weapon.get_upgrades() as u32 + 1))
+    }
+    pub fn is_drafting(&self) -> bool {
+        !self.draft_pool.is_empty()
+            && self
+                .initial_bud_teams
+                .iter()
+                .any(|initial_buds| initial_buds.len() < self.team_size)
+    }
+    /// The team whose pick it is, teams go in snake order: 1 2 3 3 2 1 1 2 3 ...
+    pub fn get_drafting_team(&self) -> u8 {
+        let team_count = self.initial_bud_teams.len().max(1);
+        let lap = self.draft_pick / team_count;
+        let position = self.draft_pick % team_count;
+        if lap.is_multiple_of(2) {
+            position as u8
+        } else {
+            (team_count - 1 - position) as u8
+        }
+    }
+    /// Fills the draft pool with buds that each have a random weapon and trait
+    pub fn roll_draft_pool(&mut self, size: usize) {
+        let (Some(tex), Some(name_generator)) = (&self.bud_texture, &self.name_generator) else {
+            return;
+        };
+        let mut rng = rand::rng();
+        self.draft_pool = (0..size)
+            .map(|_| {
+                let (weapon, weapon_string, weapon_rect) =
+                    WeaponEnum::get_weapon(rng.random_range(0..8));
+                let weapon_tex = Rc::clone(self.icon_textures.get(&weapon_string).unwrap());
+                let mut initial_bud_data = InitialBudData::default(
+                    Rc::clone(tex),
+                    0,
+                    0,
+                    name_generator,
+                    WeaponInfo::new(weapon, weapon_tex, weapon_rect),
+                );
+                let effect: Box<dyn Effect<'g>> = match rng.random_range(0..4) {
+                    0 => Box::new(FighterEffect::new()),
+                    1 => Box::new(BulwarkEffect::new()),
+                    2 => Box::new(ScoutEffect::new()),
+                    _ => Box::new(MendingEffect::new()),
+                };
+                let icon = self
+                    .icon_textures
+                    .get(&effect.name().to_lowercase())
+                    .cloned();
+                initial_bud_data.add_effect(effect, icon);
+                initial_bud_data
+            })
+            .collect();
+    }
+    /// Moves a bud from the pool to the team whose pick it is, skipping teams that are full
+    pub fn draft(&mut self, pool_index: usize) {
+        if pool_index >= self.draft_pool.len() || !self.is_drafting() {
+            return;
+        }
+        while self.initial_bud_teams[self.get_drafting_team() as usize].len() >= self.team_size {
+            self.draft_pick += 1;
+        }
+        let team = self.get_drafting_team();
+        let initial_buds = &mut self.initial_bud_teams[team as usize];
+        let mut initial_bud_data = self.draft_pool.remove(pool_index);
+        initial_bud_data.team = team;
+        initial_bud_data.index = initial_buds.len() as u8;
+        initial_bud_data.drafted = true;
+        initial_buds.push(initial_bud_data);
+        self.draft_pick += 1;
+    }
+    /// Whether the bud being edited was drafted, so only the shop can change it
+    pub fn is_current_locked(&mut self) -> bool {
+        self.get_current_initial_bud_data()
+            .is_some_and(|initial_bud_data| initial_bud_data.drafted)
+    }
+    /// Adds a fresh bud to the team being picked, if it has room and the gold
+    pub fn recruit(&mut self) {
+        let team = self.team;
+        if self.initial_bud_teams[team as usize].len() >= self.team_size {
+            return;
+        }
+        let (Some(tex), Some(name_generator), Some(sword_tex)) = (
+            &self.bud_texture,
+            &self.name_generator,
+            self.icon_textures.get("sword"),
+        ) else {
+            return;
+        };
+        let initial_bud_data = InitialBudData::default(
+            Rc::clone(tex),
+            team,
+            self.initial_bud_teams[team as usize].len() as u8,
+            name_generator,
+            WeaponInfo::default(Rc::clone(sword_tex)),
+        );
+        if self.match_info.spend(team, RECRUIT_COST) {
+            self.initial_bud_teams[team as usize].push(initial_bud_data);
+        }
+    }
+    pub fn upgrade_weapon(&mut self) {
+        let Some(cost) = self.get_upgrade_cost() else {
+            return;
+        };
+        let team = self.team;
+        if self.get_gold() < cost {
+            return;
+        }
+        if let Some(initial_bud_data) = self.get_current_initial_bud_data() {
+            if initial_bud_data.weapon_info.weapon.upgrade() {
+                self.match_info.spend(team, cost);
+            }
+        }
+    }
+    pub fn buy_trait_slot(&mut self) {
+        let team = self.team;
+        if self.get_gold() < TRAIT_SLOT_COST {
+            return;
+        }
+        if let Some(initial_bud_data) = self.get_current_initial_bud_data() {
+            if initial_bud_data.add_trait_slot() {
+                self.match_info.spend(team, TRAIT_SLOT_COST);
+            }
+        }
+    }
 }
 
 impl<'g> SelectState<'g> {
-    pub fn new(initial_buds_tuple: (Vec<InitialBudData<'g>>, Vec<InitialBudData<'g>>)) -> Self {
+    pub fn new(initial_bud_teams: Vec<Vec<InitialBudData<'g>>>) -> Self {
         // let initial_buds_tuple = (
         //     Rc::new(RefCell::new(initial_buds_tuple.0.clone())),
         //     Rc::new(RefCell::new(initial_buds_tuple.1.clone())),
@@ -84,19 +257,29 @@ impl<'g> SelectState<'g> {
                 team: 0,
                 current_bud: None,
                 trait_description: String::new(),
-                initial_buds_tuple: initial_buds_tuple.clone(),
+                initial_bud_teams: initial_bud_teams.clone(),
                 done: false,
                 icon_textures: HashMap::new(),
                 weapon_index: 0,
+                budget: None,
+                match_info: MatchInfo::new(),
+                team_size: 0,
+                player_teams: 0,
+                unlocked_traits: None,
+                bud_texture: None,
+                name_generator: None,
+                draft_pool: Vec::new(),
+                draft_pick: 0,
             })),
             msh: MenuStateHandler::new(),
             weapon_released: true,
+            drafting: false,
         }
     }
     pub fn new_state(state: &GameStateEnum<'g>) -> Box<dyn GameState<'g> + 'g> {
         match state {
-            GameStateEnum::Select(initial_buds_tuple) => {
-                Box::new(Self::new(initial_buds_tuple.clone()))
+            GameStateEnum::Select(initial_bud_teams) => {
+                Box::new(Self::new(initial_bud_teams.clone()))
             }
             _ => unreachable!(),
         }
@@ -104,12 +287,13 @@ impl<'g> SelectState<'g> {
 
     fn setup_buds(
         initial_buds: &mut Vec<InitialBudData<'g>>,
+        team_size: usize,
         team: u8,
         tex: Rc<Texture<'g>>,
         name_generator: &NameGenerator,
         weapon_tex: Rc<Texture<'g>>,
     ) {
-        while initial_buds.len() < 5 {
+        while initial_buds.len() < team_size {
             initial_buds.push(InitialBudData::default(
                 Rc::clone(&tex),
                 team,
@@ -151,26 +335,69 @@ impl<'g> GameState<'g> for SelectState<'g> {
                 .clone(),
         );
 
-        Self::setup_buds(
-            &mut self.select_info.borrow_mut().initial_buds_tuple.0,
-            0,
-            Rc::clone(&tex),
-            &name_generator,
-            Rc::clone(&sword_tex),
-        );
-        Self::setup_buds(
-            &mut self.select_info.borrow_mut().initial_buds_tuple.1,
-            1,
-            Rc::clone(&tex),
-            &name_generator,
-            Rc::clone(&sword_tex),
-        );
-        self.msh.add_menu_states(Box::new([(
-            MenuStateEnum::InitialBudDatas((Rc::clone(&self.select_info))),
-            Box::new(SelectBudState::new(gi)),
-        )]));
-        self.msh
-            .load_menu(MenuStateEnum::InitialBudDatas(Rc::clone(&self.select_info)));
+        // A draft only picks the starting teams, later rounds go on from the survivors
+        self.drafting = gi.match_settings.draft && gi.match_info.round == 0;
+        {
+            let mut select_info = self.select_info.borrow_mut();
+            select_info.budget = gi.match_settings.get_budget();
+            select_info.match_info = gi.match_info.clone();
+            select_info.team_size = gi.match_settings.team_size as usize;
+            select_info.player_teams = gi.match_settings.get_player_teams();
+            select_info.unlocked_traits = gi
+                .campaign
+                .as_ref()
+                .map(|campaign| campaign.unlocked_traits.clone());
+            let initial_bud_teams = &mut select_info.initial_bud_teams;
+            initial_bud_teams.resize(gi.match_settings.team_count as usize, Vec::new());
+            for (team, initial_buds) in initial_bud_teams.iter_mut().enumerate() {
+                if self.drafting {
+                    break;
+                }
+                // Full teams come free in the first round, after that lost buds are
+                // recruited with gold, and a wiped out team gets one bud to carry on with.
+                // Campaign battles come with both teams set
+                let team_size = if gi.campaign.is_some() {
+                    0
+                } else if gi.match_info.round == 0 {
+                    gi.match_settings.team_size as usize
+                } else {
+                    1
+                };
+                Self::setup_buds(
+                    initial_buds,
+                    team_size,
+                    team as u8,
+                    Rc::clone(&tex),
+                    &name_generator,
+                    Rc::clone(&sword_tex),
+                );
+            }
+            select_info.bud_texture = Some(tex);
+            select_info.name_generator = Some(name_generator);
+            if self.drafting {
+                let settings = &gi.match_settings;
+                let pool_size =
+                    settings.team_count as usize * (settings.team_size as usize + DRAFT_EXTRA);
+                select_info.roll_draft_pool(pool_size);
+            }
+        }
+        self.msh.add_menu_states(Box::new([
+            (
+                MenuStateEnum::InitialBudDatas(Rc::clone(&self.select_info)),
+                Box::new(SelectBudState::new(gi)),
+            ),
+            (
+                MenuStateEnum::Draft(Rc::clone(&self.select_info)),
+                Box::new(DraftState::new()),
+            ),
+        ]));
+        if self.drafting {
+            self.msh
+                .load_menu(MenuStateEnum::Draft(Rc::clone(&self.select_info)));
+        } else {
+            self.msh
+                .load_menu(MenuStateEnum::InitialBudDatas(Rc::clone(&self.select_info)));
+        }
     }
     fn run(&mut self, gi: &mut GameInfo<'g>, delta_time: f32, canvas: &mut Canvas<Window>) {
         let mouse_state = gi.input.mouse_state.clone();
@@ -184,10 +411,17 @@ impl<'g> GameState<'g> for SelectState<'g> {
         //     button.draw(canvas, &gi.camera);
         // }
         if self.select_info.borrow().done {
-            gi.game_state_handler.new_state(GameStateEnum::Arena((
-                self.select_info.borrow().initial_buds_tuple.0.clone(),
-                self.select_info.borrow().initial_buds_tuple.1.clone(),
-            )));
+            gi.match_info = self.select_info.borrow().match_info.clone();
+            gi.game_state_handler.new_state(GameStateEnum::Arena(
+                self.select_info.borrow().initial_bud_teams.clone(),
+            ));
+        }
+        // Once the rosters are full the teams look over their picks as usual
+        if self.drafting && !self.select_info.borrow().is_drafting() {
+            self.drafting = false;
+            self.select_info.borrow_mut().draft_pool.clear();
+            self.msh
+                .load_menu(MenuStateEnum::InitialBudDatas(Rc::clone(&self.select_info)));
         }
         canvas.set_draw_color(sdl2::pixels::Color::RGB(0, 255, 0));
         canvas.string(0, 0, "Select", sdl2::pixels::Color::RGB(0, 255, 0));
@@ -195,16 +429,22 @@ impl<'g> GameState<'g> for SelectState<'g> {
         self.msh.handle_state(gi, delta_time, canvas);
 
         let mut weapon_index = 0;
+        let mut upgrades = 0;
         let mut change_weapon = false;
 
-        if let Some(current_initial_bud_data) =
-            self.select_info.borrow_mut().get_current_initial_bud_data()
+        let locked = self.select_info.borrow_mut().is_current_locked();
+        if let Some(current_initial_bud_data) = self
+            .select_info
+            .borrow_mut()
+            .get_current_initial_bud_data()
+            .filter(|_| !locked)
         {
             weapon_index = current_initial_bud_data
                 .weapon_info
                 .weapon
                 .weapon_enum
                 .get_index();
+            upgrades = current_initial_bud_data.weapon_info.weapon.get_upgrades();
 
             if gi.input.is_pressed(Keycode::Right) && self.weapon_released {
                 if weapon_index == 7 {
@@ -231,7 +471,11 @@ impl<'g> GameState<'g> for SelectState<'g> {
         }
 
         if change_weapon {
-            let (weapon, weapon_string, weapon_rect) = WeaponEnum::get_weapon(weapon_index);
+            let (mut weapon, weapon_string, weapon_rect) = WeaponEnum::get_weapon(weapon_index);
+            // Upgrades are paid for with gold, so they carry over to the new weapon
+            for _ in 0..upgrades {
+                weapon.upgrade();
+            }
             let weapon_tex = Rc::clone(
                 self.select_info
                     .borrow()
@@ -266,7 +510,7 @@ pub struct NameGenerator {
 }
 
 impl NameGenerator {
-    fn new(file: impl AsRef<Path>) -> Self {
+    pub fn new(file: impl AsRef<Path>) -> Self {
         let names = NameGenerator::lines_from_file(file);
         Self { names }
     }
@@ -0,0 +1,104 @@
+use sdl2::{
+    gfx::primitives::DrawRenderer,
+    pixels::Color,
+    rect::{Point, Rect},
+    render::Canvas,
+    video::Window,
+};
+
+use crate::game::{
+    button::{Button, MenuButton},
+    game_info::GameInfo,
+    game_object::game_objects::bud::bud_data::InitialBudData,
+    game_state::{game_states::GameStateEnum, GameState},
+    match_settings::MatchSettings,
+};
+
+/// Shown between the rounds of a tournament, leads on to the next round or the podium
+pub struct StandingsState<'g> {
+    initial_bud_teams: Vec<Vec<InitialBudData<'g>>>,
+    button: MenuButton<bool>,
+    done: bool,
+}
+
+impl<'g> StandingsState<'g> {
+    pub fn new(initial_bud_teams: Vec<Vec<InitialBudData<'g>>>) -> Self {
+        Self {
+            initial_bud_teams,
+            button: MenuButton::new(
+                Rect::new(40, 80, 20, 10),
+                "Continue",
+                Box::new(|done: &mut bool| {
+                    *done = true;
+                }),
+            ),
+            done: false,
+        }
+    }
+    pub fn new_state(state: &GameStateEnum<'g>) -> Box<dyn GameState<'g> + 'g> {
+        match state {
+            GameStateEnum::Standings(initial_bud_teams) => {
+                Box::new(Self::new(initial_bud_teams.clone()))
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'g> GameState<'g> for StandingsState<'g> {
+    fn run(&mut self, gi: &mut GameInfo<'g>, _delta_time: f32, canvas: &mut Canvas<Window>) {
+        let rounds = gi.match_settings.rounds.unwrap_or(0);
+        let decided = gi.match_info.is_decided(rounds);
+        if self.done {
+            let initial_bud_teams = self.initial_bud_teams.clone();
+            gi.game_state_handler.new_state(if decided {
+                GameStateEnum::Podium(initial_bud_teams)
+            } else {
+                GameStateEnum::Select(initial_bud_teams)
+            });
+        }
+
+        let title = if decided {
+            String::from("Final Standings")
+        } else {
+            format!(
+                "Standings after round {} of {}",
+                gi.match_info.round, rounds
+            )
+        };
+        let mut point = Point::new(30, 10);
+        gi.camera.ui_point_to_camera(&mut point);
+        let _ = canvas.string(point.x as i16, point.y as i16, &title, Color::RGB(0, 0, 0));
+
+        for (place, team) in gi
+            .match_info
+            .get_standings(&self.initial_bud_teams)
+            .into_iter()
+            .enumerate()
+        {
+            let buds_left = self
+                .initial_bud_teams
+                .get(team as usize)
+                .map_or(0, |initial_buds| initial_buds.len());
+            let mut point = Point::new(30, 20 + 5 * place as i32);
+            gi.camera.ui_point_to_camera(&mut point);
+            let _ = canvas.string(
+                point.x as i16,
+                point.y as i16,
+                &format!(
+                    "{}. Team {}: {} wins, {} buds left, {} gold",
+                    place + 1,
+                    team + 1,
+                    gi.match_info.get_wins(team),
+                    buds_left,
+                    gi.match_info.get_gold(team)
+                ),
+                MatchSettings::get_team_color(team),
+            );
+        }
+
+        self.button
+            .press(&gi.input.mouse_state, &mut self.done, Some(&gi.camera));
+        self.button.draw(canvas, &gi.camera);
+    }
+}
@@ -27,7 +27,9 @@ fn get_keys() -> HashMap<Keycode, KeyState> {
             Keycode::Up,
             Keycode::Right,
             Keycode::Left,
+            Keycode::L,
             Keycode::M,
+            Keycode::P,
             Keycode::R,
             Keycode::T,
             Keycode::Num1,
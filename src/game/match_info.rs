@@ -0,0 +1,136 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::game::{
+    event_system::combat_log::CombatLog, game_object::game_objects::bud::bud_data::InitialBudData,
+};
+
+/// Gold a team starts a match with
+const STARTING_GOLD: u32 = 0;
+
+/// What a match carries from one round to the next, besides the buds themselves
+#[derive(Clone, Debug)]
+pub struct MatchInfo {
+    /// Rounds played so far
+    pub round: u32,
+    /// Gold per team, indexed by team
+    pub gold: Vec<u32>,
+    /// Rounds won per team, indexed by team
+    pub wins: Vec<u32>,
+    /// Buds that died along the way, still in the running for MVP
+    pub fallen: Vec<BudRecord>,
+    /// Rolled once per match, every round's sudden death ring comes from it
+    pub seed: u64,
+    /// Shared with the arena of every round, so the log keeps the whole match
+    pub combat_log: Rc<RefCell<CombatLog>>,
+}
+
+/// What a bud did over the match
+#[derive(Clone, Debug)]
+pub struct BudRecord {
+    pub name: String,
+    pub team: u8,
+    pub kills: u32,
+    pub damage: u32,
+}
+
+impl BudRecord {
+    pub fn new(initial_bud_data: &InitialBudData) -> Self {
+        Self {
+            name: initial_bud_data.name.clone(),
+            team: initial_bud_data.team,
+            kills: initial_bud_data.kills,
+            damage: initial_bud_data.damage_dealt,
+        }
+    }
+}
+
+impl MatchInfo {
+    pub fn new() -> Self {
+        Self {
+            round: 0,
+            gold: Vec::new(),
+            wins: Vec::new(),
+            fallen: Vec::new(),
+            seed: rand::random(),
+            combat_log: Rc::new(RefCell::new(CombatLog::new())),
+        }
+    }
+    /// Starts a fresh match for `team_count` teams
+    pub fn reset(&mut self, team_count: u8) {
+        self.round = 0;
+        self.gold = vec![STARTING_GOLD; team_count as usize];
+        self.wins = vec![0; team_count as usize];
+        self.fallen.clear();
+        self.seed = rand::random();
+        self.combat_log = Rc::new(RefCell::new(CombatLog::new()));
+    }
+    /// The seed for the round being played, the same match seed closes every round the same way
+    pub fn get_round_seed(&self) -> u64 {
+        self.seed.wrapping_add(self.round as u64)
+    }
+    pub fn get_gold(&self, team: u8) -> u32 {
+        self.gold.get(team as usize).copied().unwrap_or(0)
+    }
+    pub fn add_gold(&mut self, team: u8, amount: u32) {
+        if self.gold.len() <= team as usize {
+            self.gold.resize(team as usize + 1, STARTING_GOLD);
+        }
+        self.gold[team as usize] += amount;
+    }
+    /// Takes the gold if the team has enough
+    pub fn spend(&mut self, team: u8, amount: u32) -> bool {
+        match self.gold.get_mut(team as usize) {
+            Some(gold) if *gold >= amount => {
+                *gold -= amount;
+                true
+            }
+            _ => false,
+        }
+    }
+    pub fn get_wins(&self, team: u8) -> u32 {
+        self.wins.get(team as usize).copied().unwrap_or(0)
+    }
+    pub fn add_wins(&mut self, winners: &[u8]) {
+        for team in winners {
+            if self.wins.len() <= *team as usize {
+                self.wins.resize(*team as usize + 1, 0);
+            }
+            self.wins[*team as usize] += 1;
+        }
+    }
+    /// Whether a best-of-`rounds` tournament is over, either all rounds are played or
+    /// a team has won more than half of them
+    pub fn is_decided(&self, rounds: u8) -> bool {
+        self.round >= rounds as u32 || self.wins.iter().any(|wins| wins * 2 > rounds as u32)
+    }
+    /// Teams from most to fewest wins, a tie goes to the team with more kills and then more damage
+    pub fn get_standings(&self, initial_bud_teams: &[Vec<InitialBudData>]) -> Vec<u8> {
+        let records = self.get_records(initial_bud_teams);
+        let mut teams: Vec<u8> = (0..self.wins.len() as u8).collect();
+        teams.sort_by_key(|team| {
+            let (kills, damage) = records
+                .iter()
+                .filter(|record| record.team == *team)
+                .fold((0, 0), |(kills, damage), record| {
+                    (kills + record.kills, damage + record.damage)
+                });
+            std::cmp::Reverse((self.get_wins(*team), kills, damage))
+        });
+        teams
+    }
+    /// The bud with the most kills, then the most damage, out of the fallen and the survivors
+    pub fn get_mvp(&self, initial_bud_teams: &[Vec<InitialBudData>]) -> Option<BudRecord> {
+        self.get_records(initial_bud_teams)
+            .into_iter()
+            .max_by_key(|record| (record.kills, record.damage))
+    }
+    /// Every bud of the match, the fallen and the survivors
+    fn get_records(&self, initial_bud_teams: &[Vec<InitialBudData>]) -> Vec<BudRecord> {
+        initial_bud_teams
+            .iter()
+            .flatten()
+            .map(BudRecord::new)
+            .chain(self.fallen.iter().cloned())
+            .collect()
+    }
+}
@@ -0,0 +1,136 @@
+use sdl2::{pixels::Color, rect::Point};
+
+use crate::game::game_object::game_objects::bud::ai::Difficulty;
+
+pub const MIN_TEAMS: u8 = 2;
+pub const MAX_TEAMS: u8 = 4;
+pub const MIN_TEAM_SIZE: u8 = 1;
+pub const MAX_TEAM_SIZE: u8 = 8;
+/// Round counts a tournament can be played over
+const TOURNAMENT_ROUNDS: [u8; 3] = [3, 5, 7];
+/// Rounds sudden death can start after
+const SUDDEN_DEATH_ROUNDS: [u32; 3] = [3, 6, 9];
+/// Budget points a team gets for each of its buds
+const POINTS_PER_BUD: u16 = 5;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TeamMode {
+    /// Every team for itself
+    FreeForAll,
+    /// Teams 1 and 3 fight against teams 2 and 4
+    TeamVsTeam,
+}
+
+impl TeamMode {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            TeamMode::FreeForAll => "Free-for-all",
+            TeamMode::TeamVsTeam => "Team vs Team",
+        }
+    }
+}
+
+/// How a match is set up, picked on the home screen and kept between rounds
+#[derive(Clone, Debug)]
+pub struct MatchSettings {
+    pub team_count: u8,
+    pub mode: TeamMode,
+    pub team_size: u8,
+    /// Whether weapons and traits have to fit in a points budget
+    pub use_budget: bool,
+    /// Rounds in a best-of tournament, `None` keeps playing rounds until the players quit
+    pub rounds: Option<u8>,
+    /// Whether the starting teams are drafted from a shared pool of buds
+    pub draft: bool,
+    /// When set, every team but the first is played by the computer at this difficulty. Only
+    /// campaign battles and scenarios set it, the home screen leaves every team to the players
+    pub computer: Option<Difficulty>,
+    /// Full rounds before the arena starts closing in, `None` turns sudden death off
+    pub sudden_death: Option<u32>,
+}
+
+impl MatchSettings {
+    pub fn new() -> Self {
+        Self {
+            team_count: MIN_TEAMS,
+            mode: TeamMode::FreeForAll,
+            team_size: 5,
+            use_budget: false,
+            rounds: None,
+            draft: false,
+            computer: None,
+            sudden_death: None,
+        }
+    }
+    pub fn set_team_count(&mut self, team_count: u8) {
+        self.team_count = team_count.clamp(MIN_TEAMS, MAX_TEAMS);
+    }
+    pub fn set_team_size(&mut self, team_size: u8) {
+        self.team_size = team_size.clamp(MIN_TEAM_SIZE, MAX_TEAM_SIZE);
+    }
+    /// Steps through the tournament lengths, ending back on no tournament
+    pub fn next_rounds(&mut self) {
+        self.rounds = match self.rounds {
+            None => Some(TOURNAMENT_ROUNDS[0]),
+            Some(rounds) => TOURNAMENT_ROUNDS
+                .into_iter()
+                .find(|next_rounds| *next_rounds > rounds),
+        };
+    }
+    /// Steps through the sudden death rounds, ending back on no sudden death
+    pub fn next_sudden_death(&mut self) {
+        self.sudden_death = match self.sudden_death {
+            None => Some(SUDDEN_DEATH_ROUNDS[0]),
+            Some(round) => SUDDEN_DEATH_ROUNDS
+                .into_iter()
+                .find(|next_round| *next_round > round),
+        };
+    }
+    pub fn get_computer(&self, team: u8) -> Option<Difficulty> {
+        self.computer.filter(|_| team > 0)
+    }
+    /// Teams picked by the players, the computer's teams come after them
+    pub fn get_player_teams(&self) -> u8 {
+        if self.computer.is_some() {
+            1
+        } else {
+            self.team_count
+        }
+    }
+    /// Points each team can spend on weapons and traits, if there is a budget
+    pub fn get_budget(&self) -> Option<u16> {
+        self.use_budget
+            .then(|| self.team_size as u16 * POINTS_PER_BUD)
+    }
+    /// The side a team fights for, the last side standing wins
+    pub fn get_side(&self, team: u8) -> u8 {
+        match self.mode {
+            TeamMode::FreeForAll => team,
+            TeamMode::TeamVsTeam => team % 2,
+        }
+    }
+    pub fn is_enemy(&self, team: u8, other_team: u8) -> bool {
+        self.get_side(team) != self.get_side(other_team)
+    }
+    pub fn get_team_color(team: u8) -> Color {
+        match team {
+            0 => Color::RGB(220, 60, 60),
+            1 => Color::RGB(60, 110, 230),
+            2 => Color::RGB(60, 180, 80),
+            _ => Color::RGB(230, 200, 50),
+        }
+    }
+    /// Where a bud starts, each team lines up just off its own edge of the map and starts another
+    /// line behind the first once the edge is full. `back` moves the bud that many lines further out
+    pub fn get_spawn(team: u8, index: u8, back: i32, map_size: (u32, u32)) -> Point {
+        let width = map_size.0.max(1) as i32;
+        let height = map_size.1.max(1) as i32;
+        let index = index as i32;
+        match team {
+            0 => Point::new(-1 - back - index / height, index % height),
+            1 => Point::new(width + back + index / height, index % height),
+            2 => Point::new(index % width, -1 - back - index / width),
+            _ => Point::new(index % width, height + back + index / width),
+        }
+    }
+}
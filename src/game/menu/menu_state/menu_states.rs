@@ -8,6 +8,8 @@ use crate::game::{
 };
 
 pub mod bud_state;
+pub mod combat_log_state;
+pub mod draft_state;
 pub mod select_bud_state;
 
 use bud_state::BudState;
@@ -51,6 +53,22 @@ impl<'g> MenuStateHandler<'g> {
         }
     }
 
+    /// Loads `new_state` like `load_menu`, but while `keep` is shown it is only brought up to
+    /// date in the background, so it is current once switched back to
+    pub fn load_menu_behind(
+        &mut self,
+        gi: &mut GameInfo<'g>,
+        delta_time: f32,
+        new_state: MenuStateEnum<'g>,
+        keep: &MenuStateEnum<'g>,
+    ) {
+        if self.state.as_ref() != Some(keep) {
+            self.load_menu(new_state);
+        } else if let Some(menu_state) = self.menu_states.get_mut(&new_state) {
+            menu_state.load(gi, delta_time, &new_state);
+        }
+    }
+
     pub fn handle_state(
         &mut self,
         gi: &mut GameInfo<'g>,
@@ -83,7 +101,9 @@ impl<'g> MenuStateHandler<'g> {
 
 pub enum MenuStateEnum<'g> {
     Bud(BudEnum<'g>),
+    CombatLog,
     InitialBudDatas((Rc<RefCell<SelectInfo<'g>>>)),
+    Draft(Rc<RefCell<SelectInfo<'g>>>),
 }
 pub enum BudEnum<'g> {
     LeftBud(Option<Rc<RefCell<BudData<'g>>>>),
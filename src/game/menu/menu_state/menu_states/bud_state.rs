@@ -5,7 +5,10 @@ use sdl2::{gfx::primitives::DrawRenderer, rect::Rect, render::Canvas, video::Win
 use crate::game::{
     button::{Button, MenuButton},
     game_info::GameInfo,
-    game_object::game_objects::bud::{bud_data::BudData, Bud},
+    game_object::game_objects::bud::{
+        bud_data::{BudData, CARD_WIDTH},
+        Bud,
+    },
     menu::menu_state::{
         menu_states::{BudEnum, MenuStateEnum},
         MenuState,
@@ -126,7 +129,7 @@ impl<'g> MenuState<'g> for BudState<'g> {
             bud_data
                 .borrow()
                 .initial
-                .draw_initial_bud_data(0, canvas, &gi.camera);
+                .draw_initial_bud_data(0, CARD_WIDTH, canvas, &gi.camera);
             bud_data.borrow().draw_bud_data(0, canvas, &gi.camera);
         }
         if let Some(bud_data) = &mut self.bud_data_right {
@@ -142,7 +145,7 @@ impl<'g> MenuState<'g> for BudState<'g> {
             bud_data
                 .borrow()
                 .initial
-                .draw_initial_bud_data(4, canvas, &gi.camera);
+                .draw_initial_bud_data(4, CARD_WIDTH, canvas, &gi.camera);
             bud_data.borrow().draw_bud_data(4, canvas, &gi.camera);
         }
         hover
@@ -0,0 +1,125 @@
+use std::{cell::RefCell, rc::Rc};
+
+use sdl2::{
+    gfx::primitives::DrawRenderer,
+    rect::{Point, Rect},
+    render::Canvas,
+    video::Window,
+};
+
+use crate::game::{
+    button::{Button, MenuButton},
+    event_system::combat_log::CombatLog,
+    game_info::GameInfo,
+    match_settings::MAX_TEAMS,
+    menu::menu_state::MenuState,
+};
+
+const VISIBLE_LINES: usize = 24;
+const TEAM_LABELS: [&str; MAX_TEAMS as usize] = ["Team 1", "Team 2", "Team 3", "Team 4"];
+
+pub struct LogView {
+    /// Lines scrolled up from the newest entry
+    scroll: usize,
+    team: Option<u8>,
+}
+
+pub struct CombatLogState {
+    combat_log: Rc<RefCell<CombatLog>>,
+    view: LogView,
+    buttons: Vec<MenuButton<LogView>>,
+}
+
+impl CombatLogState {
+    pub fn new(gi: &mut GameInfo, combat_log: Rc<RefCell<CombatLog>>) -> Self {
+        let mut buttons = Vec::new();
+        buttons.push(MenuButton::new(
+            Rect::new(0, 80, 8, 5),
+            "Up",
+            Box::new(|view: &mut LogView| {
+                view.scroll += 1;
+            }),
+        ));
+        buttons.push(MenuButton::new(
+            Rect::new(8, 80, 8, 5),
+            "Down",
+            Box::new(|view: &mut LogView| {
+                view.scroll = view.scroll.saturating_sub(1);
+            }),
+        ));
+        buttons.push(MenuButton::new(
+            Rect::new(16, 80, 8, 5),
+            "All",
+            Box::new(|view: &mut LogView| {
+                view.team = None;
+                view.scroll = 0;
+            }),
+        ));
+        for team in 0..gi.match_settings.team_count {
+            buttons.push(MenuButton::new(
+                Rect::new(24 + 8 * team as i32, 80, 8, 5),
+                TEAM_LABELS[team as usize],
+                Box::new(move |view: &mut LogView| {
+                    view.team = Some(team);
+                    view.scroll = 0;
+                }),
+            ));
+        }
+        Self {
+            combat_log,
+            view: LogView {
+                scroll: 0,
+                team: None,
+            },
+            buttons,
+        }
+    }
+}
+
+impl<'g> MenuState<'g> for CombatLogState {
+    fn run(
+        &mut self,
+        gi: &mut GameInfo<'g>,
+        _delta_time: f32,
+        canvas: &mut Canvas<Window>,
+    ) -> bool {
+        let mut hover = false;
+
+        let mut rect = Rect::new(0, 20, 40, 60);
+        gi.camera.ui_rect_to_camera(&mut rect);
+        canvas.set_draw_color(sdl2::pixels::Color::RGBA(255, 255, 255, 200));
+        let _ = canvas.fill_rect(rect);
+        canvas.set_draw_color(sdl2::pixels::Color::RGB(0, 0, 0));
+        let _ = canvas.draw_rect(rect);
+
+        let combat_log = self.combat_log.borrow();
+        let entries = combat_log.get_entries(self.view.team);
+        self.view.scroll = self
+            .view
+            .scroll
+            .min(entries.len().saturating_sub(VISIBLE_LINES));
+        let end = entries.len() - self.view.scroll;
+        let start = end.saturating_sub(VISIBLE_LINES);
+        for (line, entry) in entries[start..end].iter().enumerate() {
+            let mut point = Point::new(1, 21 + line as i32 * 2);
+            gi.camera.ui_point_to_camera(&mut point);
+            let _ = canvas.string(
+                point.x as i16,
+                point.y as i16,
+                &format!("Turn {}: {}", entry.turn, entry.text),
+                sdl2::pixels::Color::RGB(0, 0, 0),
+            );
+        }
+
+        for button in self.buttons.iter_mut() {
+            if button
+                .press(&gi.input.mouse_state, &mut self.view, Some(&gi.camera))
+                .0
+            {
+                hover = true;
+            }
+            button.draw(canvas, &gi.camera);
+        }
+        hover
+    }
+}
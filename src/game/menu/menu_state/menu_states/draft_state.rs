@@ -0,0 +1,149 @@
+use std::{cell::RefCell, rc::Rc};
+
+use rand::Rng;
+use sdl2::{
+    gfx::primitives::DrawRenderer,
+    rect::{Point, Rect},
+    render::Canvas,
+    video::Window,
+};
+
+use crate::game::{
+    button::{Button, MenuButton},
+    game_info::GameInfo,
+    game_state::game_states::select_state::SelectInfo,
+    match_settings::{MatchSettings, MAX_TEAMS, MAX_TEAM_SIZE},
+    menu::menu_state::{menu_states::MenuStateEnum, MenuState},
+};
+
+/// Pool entries listed per column
+const ROWS: usize = 9;
+const ROW_WIDTH: i32 = 25;
+const ROW_HEIGHT: i32 = 5;
+/// Where the pool starts on screen
+const POOL_Y: i32 = 25;
+
+/// Lets the teams take turns picking buds from the shared draft pool
+pub struct DraftState<'g> {
+    select_info: Option<Rc<RefCell<SelectInfo<'g>>>>,
+    pool_buttons: Vec<MenuButton<SelectInfo<'g>>>,
+}
+
+impl<'g> DraftState<'g> {
+    pub fn new() -> Self {
+        // One button per place in the biggest pool there can be, the pool shrinks as buds
+        // are picked so each button takes whichever bud is listed there now
+        let places = MAX_TEAMS as usize * (MAX_TEAM_SIZE as usize + 1);
+        let pool_buttons = (0..places)
+            .map(|i| {
+                MenuButton::new(
+                    Self::get_row(i),
+                    "",
+                    Box::new(move |select_info: &mut SelectInfo<'g>| {
+                        select_info.draft(i);
+                    }),
+                )
+            })
+            .collect();
+        Self {
+            select_info: None,
+            pool_buttons,
+        }
+    }
+    fn get_row(i: usize) -> Rect {
+        Rect::new(
+            ROW_WIDTH * (i / ROWS) as i32,
+            POOL_Y + ROW_HEIGHT * (i % ROWS) as i32,
+            ROW_WIDTH as u32,
+            ROW_HEIGHT as u32,
+        )
+    }
+}
+
+impl<'g> MenuState<'g> for DraftState<'g> {
+    fn load(
+        &mut self,
+        _gi: &mut GameInfo<'g>,
+        _delta_time: f32,
+        menu_state_enum: &MenuStateEnum<'g>,
+    ) {
+        match menu_state_enum {
+            MenuStateEnum::Draft(select_info) => {
+                self.select_info = Some(Rc::clone(select_info));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn run(
+        &mut self,
+        gi: &mut GameInfo<'g>,
+        _delta_time: f32,
+        canvas: &mut Canvas<Window>,
+    ) -> bool {
+        let Some(select_info) = &self.select_info else {
+            return false;
+        };
+        let mut select_info = select_info.borrow_mut();
+        let team = select_info.get_drafting_team();
+        // The computer takes whichever bud it lands on
+        if team >= select_info.player_teams && select_info.is_drafting() {
+            let pick = rand::rng().random_range(0..select_info.draft_pool.len());
+            select_info.draft(pick);
+            return false;
+        }
+
+        let mut point = Point::new(40, 10);
+        gi.camera.ui_point_to_camera(&mut point);
+        let _ = canvas.string(
+            point.x as i16,
+            point.y as i16,
+            &format!("Draft: Team {} picks", team + 1),
+            MatchSettings::get_team_color(team),
+        );
+        for (team, initial_buds) in select_info.initial_bud_teams.iter().enumerate() {
+            let mut point = Point::new(ROW_WIDTH * team as i32, 17);
+            gi.camera.ui_point_to_camera(&mut point);
+            let _ = canvas.string(
+                point.x as i16,
+                point.y as i16,
+                &format!(
+                    "Team {}: {}/{}",
+                    team + 1,
+                    initial_buds.len(),
+                    select_info.team_size
+                ),
+                MatchSettings::get_team_color(team as u8),
+            );
+        }
+
+        let pool_size = select_info.draft_pool.len();
+        for button in self.pool_buttons.iter_mut().take(pool_size) {
+            button.press(&gi.input.mouse_state, &mut select_info, Some(&gi.camera));
+            button.draw(canvas, &gi.camera);
+        }
+        for (i, initial_bud_data) in select_info.draft_pool.iter().enumerate() {
+            let traits: Vec<&str> = initial_bud_data
+                .effects
+                .iter()
+                .flatten()
+                .map(|effect| effect.name())
+                .collect();
+            let row = Self::get_row(i);
+            let mut point = Point::new(row.x + 1, row.y + 1);
+            gi.camera.ui_point_to_camera(&mut point);
+            let _ = canvas.string(
+                point.x as i16,
+                point.y as i16,
+                &format!(
+                    "{}: {:?}, {}",
+                    initial_bud_data.name,
+                    initial_bud_data.weapon_info.weapon.weapon_enum,
+                    traits.join(", ")
+                ),
+                sdl2::pixels::Color::RGB(0, 0, 0),
+            );
+        }
+        false
+    }
+}
@@ -12,23 +12,31 @@ use crate::game::{
     button::{Button, HoverMenuButton, MenuButton},
     camera::Camera,
     effect_system::effects::self_effect::{
-        BulwarkEffect, FighterEffect, MendingEffect, ScoutEffect, SelfEffect,
+        BulwarkEffect, EntanglingEffect, FighterEffect, MendingEffect, ScoutEffect, SelfEffect,
+        VenomousEffect,
     },
     game_info::GameInfo,
     game_object::game_objects::bud::{
-        bud_data::{BudData, InitialBudData},
+        bud_data::{BudData, InitialBudData, CARD_WIDTH},
         weapon::Weapon,
         Bud,
     },
-    game_state::game_states::select_state::SelectInfo,
+    game_state::game_states::select_state::{SelectInfo, RECRUIT_COST, TRAIT_SLOT_COST},
+    match_settings::MatchSettings,
     menu::menu_state::{
         menu_states::{BudEnum, MenuStateEnum},
         MenuState,
     },
 };
 
+/// Cards share the width of the screen, so bigger teams get narrower cards
+fn get_card_width(team_size: u8) -> i32 {
+    (100 / team_size.max(1) as i32).min(CARD_WIDTH)
+}
+
 pub struct SelectBudState<'g> {
     select_info: Option<Rc<RefCell<SelectInfo<'g>>>>,
+    card_width: i32,
     full_buttons: Vec<MenuButton<SelectInfo<'g>>>,
     edit_buttons: Vec<MenuButton<SelectInfo<'g>>>,
     trait_buttons: Vec<HoverMenuButton<'g, SelectInfo<'g>>>,
@@ -41,48 +49,36 @@ impl<'g> SelectBudState<'g> {
             Rect::new(50, 80, 50, 20),
             "Confirm",
             Box::new(|select_info: &mut SelectInfo<'g>| {
-                if select_info.team == 0 {
-                    select_info.team = 1;
+                if !select_info.within_budget() {
+                    return;
+                }
+                if select_info.team + 1 < select_info.player_teams {
+                    select_info.team += 1;
                 } else {
                     select_info.done = true;
                 }
             }),
         ));
         full_buttons.push(MenuButton::new(
-            Rect::new(20 * 0, 20, 20, 60),
-            "",
-            Box::new(|select_info: &mut SelectInfo<'g>| {
-                select_info.current_bud = Some(0);
-            }),
-        ));
-        full_buttons.push(MenuButton::new(
-            Rect::new(20 * 1, 20, 20, 60),
-            "",
-            Box::new(|select_info: &mut SelectInfo<'g>| {
-                select_info.current_bud = Some(1);
-            }),
-        ));
-        full_buttons.push(MenuButton::new(
-            Rect::new(20 * 2, 20, 20, 60),
-            "",
-            Box::new(|select_info: &mut SelectInfo<'g>| {
-                select_info.current_bud = Some(2);
-            }),
-        ));
-        full_buttons.push(MenuButton::new(
-            Rect::new(20 * 3, 20, 20, 60),
-            "",
-            Box::new(|select_info: &mut SelectInfo<'g>| {
-                select_info.current_bud = Some(3);
-            }),
-        ));
-        full_buttons.push(MenuButton::new(
-            Rect::new(20 * 4, 20, 20, 60),
-            "",
+            Rect::new(0, 80, 25, 20),
+            "Recruit",
             Box::new(|select_info: &mut SelectInfo<'g>| {
-                select_info.current_bud = Some(4);
+                select_info.recruit();
             }),
         ));
+        let card_width = get_card_width(gi.match_settings.team_size);
+        for i in 0..gi.match_settings.team_size as usize {
+            full_buttons.push(MenuButton::new(
+                Rect::new(card_width * i as i32, 20, card_width as u32, 60),
+                "",
+                Box::new(move |select_info: &mut SelectInfo<'g>| {
+                    // Slots past the team's buds are empty until someone is recruited
+                    if i < select_info.initial_bud_teams[select_info.team as usize].len() {
+                        select_info.current_bud = Some(i);
+                    }
+                }),
+            ));
+        }
 
         let mut edit_buttons = Vec::new();
 
@@ -102,11 +98,26 @@ impl<'g> SelectBudState<'g> {
                 }
             }),
         ));
+        edit_buttons.push(MenuButton::new(
+            Rect::new(20, 75, 15, 5),
+            "Upgrade Weapon",
+            Box::new(|select_info: &mut SelectInfo<'g>| {
+                select_info.upgrade_weapon();
+            }),
+        ));
+        edit_buttons.push(MenuButton::new(
+            Rect::new(35, 75, 15, 5),
+            "Trait Slot",
+            Box::new(|select_info: &mut SelectInfo<'g>| {
+                select_info.buy_trait_slot();
+            }),
+        ));
 
         let mut trait_buttons = Vec::new();
 
         Self {
             select_info: None,
+            card_width,
             full_buttons,
             edit_buttons,
             trait_buttons,
@@ -253,8 +264,9 @@ impl<'g> MenuState<'g> for SelectBudState<'g> {
                         }
                     }),
                     Box::new(|select_info: &mut SelectInfo<'g>| {
-                        select_info.trait_description =
-                            String::from("Restore +1 health at the start of each turn.");
+                        select_info.trait_description = String::from(
+                            "Restore +1 health and shake off harmful statuses at the start of each turn.",
+                        );
                     }),
                 ));
                 self.trait_buttons.push(HoverMenuButton::new(
@@ -525,6 +537,50 @@ impl<'g> MenuState<'g> for SelectBudState<'g> {
                         );
                     }),
                 ));
+                self.trait_buttons.push(HoverMenuButton::new(
+                    Rect::new(60, 20, 12, 12),
+                    "Venomous",
+                    Rc::clone(select_info.icon_textures.get("venomous").unwrap()),
+                    Box::new(|select_info: &mut SelectInfo<'g>| {
+                        let tex = if let Some(tex) = select_info.icon_textures.get("venomous") {
+                            Some(Rc::clone(tex))
+                        } else {
+                            None
+                        };
+                        if let Some(current_initial_bud_data) =
+                            select_info.get_current_initial_bud_data()
+                        {
+                            current_initial_bud_data
+                                .add_effect(Box::new(VenomousEffect::new()), tex);
+                        }
+                    }),
+                    Box::new(|select_info: &mut SelectInfo<'g>| {
+                        select_info.trait_description =
+                            String::from("Hits poison the target, dealing 1 damage at the start of its next 2 turns.");
+                    }),
+                ));
+                self.trait_buttons.push(HoverMenuButton::new(
+                    Rect::new(60, 30, 12, 12),
+                    "Entangling",
+                    Rc::clone(select_info.icon_textures.get("entangling").unwrap()),
+                    Box::new(|select_info: &mut SelectInfo<'g>| {
+                        let tex = if let Some(tex) = select_info.icon_textures.get("entangling") {
+                            Some(Rc::clone(tex))
+                        } else {
+                            None
+                        };
+                        if let Some(current_initial_bud_data) =
+                            select_info.get_current_initial_bud_data()
+                        {
+                            current_initial_bud_data
+                                .add_effect(Box::new(EntanglingEffect::new()), tex);
+                        }
+                    }),
+                    Box::new(|select_info: &mut SelectInfo<'g>| {
+                        select_info.trait_description =
+                            String::from("Hits root the target, it can not move on its next turn.");
+                    }),
+                ));
             }
             _ => unreachable!(),
         }
@@ -535,10 +591,60 @@ impl<'g> MenuState<'g> for SelectBudState<'g> {
         if let Some(select_info) = &self.select_info {
             let mut select_info = select_info.borrow_mut();
 
+            let mut point = Point::new(50, 15);
+            gi.camera.ui_point_to_camera(&mut point);
+            let _ = canvas.string(
+                point.x as i16,
+                point.y as i16,
+                &format!("Team {}", select_info.team + 1),
+                MatchSettings::get_team_color(select_info.team),
+            );
+            if let Some(budget) = select_info.budget {
+                let mut point = Point::new(50, 17);
+                gi.camera.ui_point_to_camera(&mut point);
+                let color = if select_info.within_budget() {
+                    sdl2::pixels::Color::RGB(0, 0, 0)
+                } else {
+                    sdl2::pixels::Color::RGB(200, 0, 0)
+                };
+                let _ = canvas.string(
+                    point.x as i16,
+                    point.y as i16,
+                    &format!("Points: {} / {}", select_info.get_team_cost(), budget),
+                    color,
+                );
+            }
+            let mut point = Point::new(50, 19);
+            gi.camera.ui_point_to_camera(&mut point);
+            let gold = match select_info.get_upgrade_cost() {
+                Some(upgrade_cost) => format!(
+                    "Gold: {} | Upgrade: {} | Trait Slot: {}",
+                    select_info.get_gold(),
+                    upgrade_cost,
+                    TRAIT_SLOT_COST
+                ),
+                None => format!(
+                    "Gold: {} | Recruit: {}",
+                    select_info.get_gold(),
+                    RECRUIT_COST
+                ),
+            };
+            let _ = canvas.string(
+                point.x as i16,
+                point.y as i16,
+                &gold,
+                sdl2::pixels::Color::RGB(0, 0, 0),
+            );
+
             if let Some(current_bud) = select_info.current_bud {
                 // Edit Mode
 
+                // Drafted buds can only be changed through the shop
+                let locked = select_info.is_current_locked();
                 for button in self.edit_buttons.iter_mut() {
+                    if locked && button.get_draw_values().1 == "Reset" {
+                        continue;
+                    }
                     button
                         .press(&gi.input.mouse_state, &mut select_info, Some(&gi.camera))
                         .0;
@@ -546,15 +652,29 @@ impl<'g> MenuState<'g> for SelectBudState<'g> {
                     button.draw(canvas, &gi.camera);
                 }
                 for button in self.trait_buttons.iter_mut() {
+                    if locked || !select_info.is_unlocked(button.get_draw_values().1) {
+                        continue;
+                    }
                     button
                         .press(&gi.input.mouse_state, &mut select_info, Some(&gi.camera))
                         .0;
 
                     button.draw(canvas, &gi.camera);
                 }
+                if locked {
+                    let mut point = Point::new(20, 20);
+                    gi.camera.ui_point_to_camera(&mut point);
+                    let _ = canvas.string(
+                        point.x as i16,
+                        point.y as i16,
+                        "Drafted, the weapon and traits are locked",
+                        sdl2::pixels::Color::RGB(0, 0, 0),
+                    );
+                }
 
                 if let Some(current_initial_bud_data) = select_info.get_current_initial_bud_data() {
-                    current_initial_bud_data.draw_initial_bud_data(0, canvas, &gi.camera);
+                    current_initial_bud_data
+                        .draw_initial_bud_data(0, CARD_WIDTH, canvas, &gi.camera);
                 }
 
                 let mut rect = Rect::new(80, 20, 20, 60);
@@ -580,13 +700,15 @@ impl<'g> MenuState<'g> for SelectBudState<'g> {
                         .0;
                     button.draw(canvas, &gi.camera);
 
-                    let initial_bud_datas = if select_info.team == 0 {
-                        &mut select_info.initial_buds_tuple.0
-                    } else {
-                        &mut select_info.initial_buds_tuple.1
-                    };
+                    let team = select_info.team as usize;
+                    let initial_bud_datas = &mut select_info.initial_bud_teams[team];
                     for (i, initial_bud_data) in initial_bud_datas.iter_mut().enumerate() {
-                        initial_bud_data.draw_initial_bud_data(i as i32, canvas, &gi.camera);
+                        initial_bud_data.draw_initial_bud_data(
+                            i as i32,
+                            self.card_width,
+                            canvas,
+                            &gi.camera,
+                        );
                     }
                 }
             }
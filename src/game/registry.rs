@@ -0,0 +1,32 @@
+/// Stable id given to every object in a scene, shared by the scene, turn and collision lists
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct ObjectId(u32);
+
+/// Hands out ids and collects the ids of removed objects,
+/// so every list can drop the same objects at the same time
+pub struct Registry {
+    next_id: u32,
+    removed: Vec<ObjectId>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self {
+            next_id: 0,
+            removed: Vec::new(),
+        }
+    }
+    pub fn new_id(&mut self) -> ObjectId {
+        let id = ObjectId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+    pub fn remove(&mut self, id: ObjectId) {
+        if !self.removed.contains(&id) {
+            self.removed.push(id);
+        }
+    }
+    pub fn take_removed(&mut self) -> Vec<ObjectId> {
+        std::mem::take(&mut self.removed)
+    }
+}
@@ -0,0 +1,266 @@
+use std::{collections::HashMap, fs, path::Path, rc::Rc};
+
+use sdl2::{rect::Point, render::Texture};
+
+use crate::game::{
+    campaign::{read_pairs, weapon_index, SavedBud, Victory, SAVE_DIR},
+    game_object::game_objects::bud::{ai::Difficulty, bud_data::InitialBudData, Direction},
+    match_settings::MatchSettings,
+};
+
+const SCENARIO_DIR: &str = "assets/scenarios";
+const SAVE_FILE: &str = "saves/scenarios.txt";
+
+fn direction_from_name(name: &str) -> Option<Direction> {
+    match name {
+        "up" => Some(Direction::Up),
+        "right" => Some(Direction::Right),
+        "left" => Some(Direction::Left),
+        "down" => Some(Direction::Down),
+        _ => None,
+    }
+}
+
+/// A bud exactly as a scenario puts it down
+#[derive(Clone, Debug)]
+pub struct ScenarioBud {
+    pub team: u8,
+    pub position: Point,
+    pub direction: Direction,
+    /// Health the bud starts on, `None` starts it at full health
+    pub health: Option<u16>,
+    pub bud: SavedBud,
+}
+
+impl ScenarioBud {
+    /// Written as `team, x, y, facing, health, name, weapon, trait, trait`, with `full` health
+    /// for an unhurt bud
+    fn from_value(value: &str) -> Option<Self> {
+        let mut parts = value.split(',').map(str::trim);
+        let team = parts.next()?.parse().ok()?;
+        let position = Point::new(parts.next()?.parse().ok()?, parts.next()?.parse().ok()?);
+        let direction = direction_from_name(parts.next()?)?;
+        let health = match parts.next()? {
+            "full" => None,
+            health => Some(health.parse().ok()?),
+        };
+        let name = parts.next()?.to_string();
+        let weapon = weapon_index(parts.next()?)?;
+        let traits = parts.map(String::from).collect();
+        Some(Self {
+            team,
+            position,
+            direction,
+            health,
+            bud: SavedBud::recruit(name, weapon, traits),
+        })
+    }
+}
+
+/// A puzzle with everything fixed up front, read from a file in the scenario folder. Buds take
+/// their turns in the order they are listed
+#[derive(Clone, Debug)]
+pub struct Scenario {
+    /// The file the scenario was read from, which is what completion is saved under
+    pub file: String,
+    pub name: String,
+    pub description: String,
+    pub map: String,
+    pub victory: Victory,
+    /// Turns the player's buds get between them before the puzzle is lost
+    pub turns: Option<u32>,
+    pub difficulty: Difficulty,
+    pub buds: Vec<ScenarioBud>,
+}
+
+impl Scenario {
+    /// `None` if the file can't be read
+    pub fn load(file: &str) -> Option<Scenario> {
+        let mut scenario = Scenario {
+            file: file.to_string(),
+            name: file.to_string(),
+            description: String::new(),
+            map: String::from("tile_map_1.tm"),
+            victory: Victory::Rout,
+            turns: None,
+            difficulty: Difficulty::Easy,
+            buds: Vec::new(),
+        };
+        let pairs = match read_pairs(format!("{SCENARIO_DIR}/{file}")) {
+            Ok(pairs) => pairs,
+            Err(e) => {
+                println!("Could not read scenario {file}: {e}");
+                return None;
+            }
+        };
+        for (key, value) in pairs {
+            match key.as_str() {
+                "name" => scenario.name = value,
+                "description" => scenario.description = value,
+                "map" => scenario.map = value,
+                "victory" => {
+                    scenario.victory = Victory::from_value(&value).unwrap_or(scenario.victory)
+                }
+                "turns" => scenario.turns = value.parse().ok(),
+                "difficulty" => {
+                    scenario.difficulty =
+                        Difficulty::from_name(&value).unwrap_or(scenario.difficulty)
+                }
+                "bud" => match ScenarioBud::from_value(&value) {
+                    Some(bud) => scenario.buds.push(bud),
+                    None => println!("Could not read bud {value} in scenario {file}"),
+                },
+                _ => println!("Unknown key {key} in scenario {file}"),
+            }
+        }
+        Some(scenario)
+    }
+    /// Every scenario in the scenario folder, in file name order
+    pub fn load_all() -> Vec<Scenario> {
+        let mut files: Vec<String> = fs::read_dir(SCENARIO_DIR)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .filter(|file| file.ends_with(".txt"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        files.sort();
+        files
+            .iter()
+            .filter_map(|file| Scenario::load(file))
+            .collect()
+    }
+    pub fn get_team_count(&self) -> u8 {
+        self.buds.iter().map(|bud| bud.team + 1).max().unwrap_or(0)
+    }
+    /// The buds of every team, in the order they are listed in the file
+    pub fn get_teams(&self) -> Vec<Vec<&ScenarioBud>> {
+        (0..self.get_team_count())
+            .map(|team| self.buds.iter().filter(|bud| bud.team == team).collect())
+            .collect()
+    }
+    /// The teams ready for the arena, the first team gets `texture` and the rest `enemy_texture`
+    pub fn make_teams<'g>(
+        &self,
+        texture: &Rc<Texture<'g>>,
+        enemy_texture: &Rc<Texture<'g>>,
+        icons: &HashMap<String, Rc<Texture<'g>>>,
+    ) -> Vec<Vec<InitialBudData<'g>>> {
+        self.get_teams()
+            .iter()
+            .enumerate()
+            .map(|(team, buds)| {
+                let texture = if team == 0 { texture } else { enemy_texture };
+                buds.iter()
+                    .enumerate()
+                    .filter_map(|(i, bud)| {
+                        bud.bud
+                            .to_initial(team as u8, i as u8, Rc::clone(texture), icons)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+    /// Where the bud at `index` of `team` is put down
+    pub fn get_placement(&self, team: u8, index: u8) -> Option<&ScenarioBud> {
+        self.buds
+            .iter()
+            .filter(|bud| bud.team == team)
+            .nth(index as usize)
+    }
+    /// Where the bud is listed in the file, which is also when it takes its turn
+    pub fn get_turn(&self, team: u8, index: u8) -> Option<usize> {
+        self.buds
+            .iter()
+            .enumerate()
+            .filter(|(_, bud)| bud.team == team)
+            .nth(index as usize)
+            .map(|(turn, _)| turn)
+    }
+    /// Settings that hand every team but the first to the computer
+    pub fn make_settings(&self) -> MatchSettings {
+        let mut settings = MatchSettings::new();
+        settings.team_count = self.get_team_count();
+        let team_size = self.get_teams().iter().map(Vec::len).max().unwrap_or(1);
+        settings.set_team_size(team_size as u8);
+        settings.computer = Some(self.difficulty);
+        settings
+    }
+}
+
+/// The scenarios solved so far, kept on disk between sessions
+#[derive(Clone, Debug)]
+pub struct ScenarioProgress {
+    pub completed: Vec<String>,
+}
+
+impl ScenarioProgress {
+    /// The saved progress, or none solved if there is no save or it can't be read
+    pub fn load() -> Self {
+        let mut progress = Self {
+            completed: Vec::new(),
+        };
+        if !Path::new(SAVE_FILE).exists() {
+            return progress;
+        }
+        let pairs = match read_pairs(SAVE_FILE) {
+            Ok(pairs) => pairs,
+            Err(e) => {
+                println!("Could not read the scenario save, starting over: {e}");
+                return progress;
+            }
+        };
+        for (key, value) in pairs {
+            if key == "completed" && !progress.is_completed(&value) {
+                progress.completed.push(value);
+            }
+        }
+        progress
+    }
+    pub fn save(&self) {
+        let lines: Vec<String> = self
+            .completed
+            .iter()
+            .map(|file| format!("completed: {file}"))
+            .collect();
+        if let Err(e) =
+            fs::create_dir_all(SAVE_DIR).and_then(|_| fs::write(SAVE_FILE, lines.join("\n") + "\n"))
+        {
+            println!("Could not save the scenarios: {e}");
+        }
+    }
+    pub fn is_completed(&self, file: &str) -> bool {
+        self.completed.iter().any(|completed| completed == file)
+    }
+}
+
+/// The scenario being played, kept in `GameInfo` until it is won or lost
+pub struct ScenarioRun {
+    pub scenario: Scenario,
+    /// The settings from before the scenario, put back once it is over
+    previous_settings: MatchSettings,
+}
+
+impl ScenarioRun {
+    pub fn new(scenario: Scenario, previous_settings: MatchSettings) -> Self {
+        Self {
+            scenario,
+            previous_settings,
+        }
+    }
+    /// Gives up on the scenario without marking it
+    pub fn abandon(self) -> MatchSettings {
+        self.previous_settings
+    }
+    /// Marks the scenario solved on a win
+    pub fn finish(self, won: bool) -> MatchSettings {
+        let mut progress = ScenarioProgress::load();
+        if won && !progress.is_completed(&self.scenario.file) {
+            progress.completed.push(self.scenario.file);
+            progress.save();
+        }
+        self.previous_settings
+    }
+}
@@ -8,6 +8,7 @@ use crate::game::{
     game_object::{game_objects::GameObjectEnum, GameObject, SuperGameObject},
     game_state::StateInfo,
     menu::menu_state::menu_states::MenuStateHandler,
+    registry::ObjectId,
 };
 
 use super::{camera::Camera, collision_system::collisions::Collisions, input::Input};
@@ -15,7 +16,7 @@ use super::{camera::Camera, collision_system::collisions::Collisions, input::Inp
 pub type Object<'g> = dyn SuperGameObject<'g, Input = MenuStateHandler<'g>> + 'g;
 
 pub struct SceneManager<'g> {
-    pub object_list: Vec<Rc<RefCell<Object<'g>>>>,
+    pub object_list: Vec<(ObjectId, Rc<RefCell<Object<'g>>>)>,
     // ui_List: Vec<UIObject>,
 }
 
@@ -25,22 +26,17 @@ impl<'g> SceneManager<'g> {
             object_list: Vec::new(),
         }
     }
-    pub fn add(&mut self, obj: Rc<RefCell<Object<'g>>>) {
-        self.object_list.push(obj);
+    pub fn add(&mut self, id: ObjectId, obj: Rc<RefCell<Object<'g>>>) {
+        self.object_list.push((id, obj));
     }
-    pub fn remove(&mut self, index: usize) {
-        self.object_list.remove(index);
+    pub fn remove(&mut self, id: ObjectId) {
+        self.object_list.retain(|(other_id, _)| *other_id != id);
     }
-    pub fn remove_all(&mut self, indexes: &[usize], collisions: &mut Collisions) {
-        for (i, index) in indexes.iter().enumerate() {
-            // self.object_list
-            //     .get(*index - i)
-            //     .unwrap()
-            //     .borrow_mut()
-            //     .remove_collider(collisions);
-            self.object_list.remove(*index - i);
-        }
+    pub fn remove_all(&mut self, ids: &[ObjectId]) {
+        self.object_list
+            .retain(|(other_id, _)| !ids.contains(other_id));
     }
+    /// Objects that are done updating are handed to the registry, which removes them everywhere
     pub fn update(
         &mut self,
         delta_time: f32,
@@ -48,23 +44,20 @@ impl<'g> SceneManager<'g> {
         gi: &mut GameInfo<'g>,
         si: &mut StateInfo<'g>,
     ) {
-        let mut indexes = Vec::new();
-        for (i, obj) in self.object_list.iter_mut().enumerate() {
+        for (id, obj) in self.object_list.iter_mut() {
             if !obj.borrow_mut().update(delta_time, collisions, gi, si) {
-                indexes.push(i);
+                si.registry.remove(*id);
             }
         }
-        self.remove_all(&indexes, collisions);
-        collisions.remove_all(&indexes);
     }
     pub fn press(&mut self, gi: &mut GameInfo, msh: &mut MenuStateHandler<'g>) {
-        for (i, obj) in self.object_list.iter_mut().enumerate() {
+        for (_, obj) in self.object_list.iter_mut() {
             obj.borrow_mut()
                 .press(&gi.input.mouse_state, msh, Some(&gi.camera));
         }
     }
     pub fn draw(&self, canvas: &mut Canvas<Window>, camera: &mut Camera) {
-        for obj in self.object_list.iter() {
+        for (_, obj) in self.object_list.iter() {
             // obj.borrow().draw(canvas, camera);
             // let obj = &obj.borrow();
             GameObject::draw(&*(obj.borrow()), canvas, camera);
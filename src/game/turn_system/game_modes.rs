@@ -1,8 +1,10 @@
 use crate::game::{
-    collision_system::collisions::Collisions, game_info::GameInfo, game_state::StateInfo,
+    collision_system::collisions::Collisions, game_info::GameInfo,
+    game_object::game_objects::pickup::PickupSpawner, game_state::StateInfo,
 };
 
 pub mod death_match;
+pub mod sudden_death;
 
 pub trait GameMode<'g> {
     fn setup(
@@ -22,6 +24,14 @@ pub trait GameMode<'g> {
     );
     fn draw(&mut self, collisions: &mut Collisions, gi: &mut GameInfo<'g>, si: &mut StateInfo<'g>) {
     }
+    /// Drops pickups onto the field whenever the mode calls for them
+    fn spawn_pickups(
+        &mut self,
+        _collisions: &Collisions<'g>,
+        _si: &mut StateInfo<'g>,
+        _pickups: &PickupSpawner<'g>,
+    ) {
+    }
 }
 
 // pub enum RoundState {
@@ -1,24 +1,58 @@
+use std::{cell::RefCell, rc::Rc};
+
 use rand::Rng;
 
 use crate::game::{
-    collision_system::collisions::Collisions, game_info::GameInfo, game_state::StateInfo,
+    collision_system::collisions::Collisions,
+    event_system::events::{CombatEvent, EventListener},
+    game_info::GameInfo,
+    game_object::game_objects::pickup::PickupSpawner,
+    game_state::StateInfo,
     turn_system::game_modes::GameMode,
 };
 
+/// A random pickup shows up every this many turns
+const PICKUP_TURNS: u32 = 8;
+
 #[derive(Debug)]
 pub struct DeathMatch {
     required_kills: u8,
     kings: Vec<u8>,
+    /// Buds per team at the start of the round
+    team_sizes: Vec<u8>,
+    /// Deaths per team, counted from the event bus
+    deaths: Vec<u8>,
+    king_deaths: Vec<u8>,
+    /// Pickups owed to the field, counted from turn starts
+    pickups_due: u8,
+    /// Whether random pickups show up at all
+    pickups: bool,
+    /// Turns the first team's buds get between them, it is out once they are used up
+    turn_limit: Option<u32>,
+    first_team_turns: u32,
+    done: bool,
+    /// The winners of a finished round, held back a frame so `RoundEnd` reaches every listener
+    /// before the round is wrapped up
+    winners: Option<Vec<u8>>,
 }
 
 impl DeathMatch {
-    pub fn new(required_kills: u8, kings: Vec<u8>) -> DeathMatch {
+    pub fn new(required_kills: u8, kings: Vec<u8>, team_sizes: Vec<u8>) -> DeathMatch {
         DeathMatch {
             required_kills,
             kings,
+            deaths: vec![0; team_sizes.len()],
+            king_deaths: vec![0; team_sizes.len()],
+            team_sizes,
+            pickups_due: 0,
+            pickups: true,
+            turn_limit: None,
+            first_team_turns: 0,
+            done: false,
+            winners: None,
         }
     }
-    pub fn random() -> DeathMatch {
+    pub fn random(team_sizes: Vec<u8>) -> DeathMatch {
         let mut rng = rand::thread_rng(); // Get a thread-local random number generator
         let required_kills = rng.random_range(1..=5);
         let kings_count = rng.random_range(0..=required_kills);
@@ -29,9 +63,66 @@ impl DeathMatch {
                 kings.push(king);
             }
         }
-        DeathMatch {
-            required_kills,
-            kings,
+        DeathMatch::new(required_kills, kings, team_sizes)
+    }
+    pub fn without_pickups(mut self) -> DeathMatch {
+        self.pickups = false;
+        self
+    }
+    pub fn with_turn_limit(mut self, turns: u32) -> DeathMatch {
+        self.turn_limit = Some(turns);
+        self
+    }
+    /// A team is out once every bud is dead, or it lost as many as the mode allows
+    fn is_eliminated(&self, team: usize) -> bool {
+        let size = self.team_sizes[team];
+        if self.deaths[team] >= size {
+            return true;
+        }
+        if self.kings.is_empty() {
+            // DeathMatch
+            self.deaths[team] >= self.required_kills
+        } else {
+            // Chess, kings past the end of a shrunken team don't count
+            let kings = self.kings.iter().filter(|king| **king < size).count() as u8;
+            kings > 0 && self.king_deaths[team] >= kings
+        }
+    }
+    /// Whether the first team has started more turns than it gets
+    fn is_out_of_turns(&self) -> bool {
+        self.turn_limit
+            .is_some_and(|turn_limit| self.first_team_turns > turn_limit)
+    }
+    /// The teams still in the fight, in any order of elimination
+    fn get_standing(&self) -> Vec<u8> {
+        (0..self.team_sizes.len())
+            .filter(|team| !self.is_eliminated(*team))
+            .map(|team| team as u8)
+            .collect()
+    }
+}
+
+impl EventListener for DeathMatch {
+    fn on_event(&mut self, turn: u32, event: &CombatEvent) {
+        if let CombatEvent::TurnStart { bud } = event {
+            if bud.team == 0 {
+                self.first_team_turns += 1;
+            }
+            if self.pickups && turn > 0 && turn.is_multiple_of(PICKUP_TURNS) {
+                self.pickups_due += 1;
+            }
+        }
+        if let CombatEvent::Death { bud, .. } = event {
+            let team = bud.team as usize;
+            if self.deaths.len() <= team {
+                self.deaths.resize(team + 1, 0);
+                self.king_deaths.resize(team + 1, 0);
+                self.team_sizes.resize(team + 1, 0);
+            }
+            self.deaths[team] += 1;
+            if self.kings.contains(&bud.index) {
+                self.king_deaths[team] += 1;
+            }
         }
     }
 }
@@ -55,52 +146,34 @@ impl<'g> GameMode<'g> for DeathMatch {
         gi: &mut GameInfo<'g>,
         si: &mut StateInfo<'g>,
     ) {
-        if self.kings.is_empty() {
-            // DeathMatch
-            let mut death_count = 0;
-            for bud_data in si.bud_data_tuple.0.iter() {
-                if !bud_data.borrow().alive() {
-                    death_count += 1;
-                }
-            }
-            if death_count >= self.required_kills {
-                // Team 2 Wins
-                si.end_round(gi);
-            }
-
-            let mut death_count = 0;
-            for bud_data in si.bud_data_tuple.0.iter() {
-                if !bud_data.borrow().alive() {
-                    death_count += 1;
-                }
-            }
-            if death_count >= self.required_kills {
-                // Team 1 Wins
-                si.end_round(gi);
-            }
-        } else {
-            // Chess
-            let mut death_count = 0;
-            for (index, bud_data) in si.bud_data_tuple.0.iter().enumerate() {
-                if !bud_data.borrow().alive() && self.kings.contains(&(index as u8)) {
-                    death_count += 1;
-                }
-            }
-            if death_count >= self.kings.len() {
-                // Team 2 Wins
-                si.end_round(gi);
-            }
-
-            let mut death_count = 0;
-            for (index, bud_data) in si.bud_data_tuple.0.iter().enumerate() {
-                if !bud_data.borrow().alive() && self.kings.contains(&(index as u8)) {
-                    death_count += 1;
-                }
-            }
-            if death_count >= self.kings.len() {
-                // Team 2 Wins
-                si.end_round(gi);
-            }
+        if let Some(winners) = self.winners.take() {
+            si.end_round(gi, &winners);
+            return;
+        }
+        if self.done {
+            return;
+        }
+        let count_sides = |standing: &[u8]| {
+            let mut sides: Vec<u8> = standing
+                .iter()
+                .map(|team| gi.match_settings.get_side(*team))
+                .collect();
+            sides.sort();
+            sides.dedup();
+            sides.len()
+        };
+        let mut standing = self.get_standing();
+        // The winning blow can land just before the first team's next turn starts, so running
+        // out of turns only counts while the fight is still open
+        if self.is_out_of_turns() && count_sides(&standing) > 1 {
+            standing.retain(|team| *team != 0);
+        }
+        if count_sides(&standing) <= 1 {
+            self.done = true;
+            si.events.borrow_mut().emit(CombatEvent::RoundEnd {
+                winners: standing.clone(),
+            });
+            self.winners = Some(standing);
         }
     }
     fn draw(&mut self, collisions: &mut Collisions, gi: &mut GameInfo<'g>, si: &mut StateInfo<'g>) {
@@ -110,4 +183,17 @@ impl<'g> GameMode<'g> for DeathMatch {
             // Chess
         }
     }
+    fn spawn_pickups(
+        &mut self,
+        collisions: &Collisions<'g>,
+        si: &mut StateInfo<'g>,
+        pickups: &PickupSpawner<'g>,
+    ) {
+        for _ in 0..self.pickups_due {
+            if let Some(pickup) = pickups.random(collisions) {
+                si.add_colliding_object(Rc::new(RefCell::new(pickup)));
+            }
+        }
+        self.pickups_due = 0;
+    }
 }
@@ -0,0 +1,156 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use sdl2::{
+    pixels::Color,
+    rect::{Point, Rect},
+    render::Canvas,
+    video::Window,
+};
+
+use crate::{
+    game::{
+        button::NoButton,
+        camera::Camera,
+        collision_system::collisions::Collisions,
+        effect_system::effects::self_effect::DamageEffect,
+        event_system::events::{CombatEvent, EventListener},
+        game_info::GameInfo,
+        game_object::GameObject,
+        game_state::StateInfo,
+        registry::ObjectId,
+    },
+    vector2d::Vector2d,
+};
+
+/// Damage a bud takes for starting its turn outside the safe area
+const HAZARD_DAMAGE: u16 = 1;
+
+/// Works with any game mode: once enough rounds have gone by, the arena closes in a ring
+/// every round and buds starting their turn outside of it get hurt
+pub struct SuddenDeath {
+    /// Full rounds played before the arena starts closing in
+    start_round: u32,
+    rounds: u32,
+    /// Buds that started a turn this round, a repeat means a new round
+    seen: Vec<ObjectId>,
+    /// Corners of the area that is still safe
+    safe: (Point, Point),
+    /// Corners of the whole arena, everything past them is hazard from the start
+    arena: (Point, Point),
+    /// The tile the safe area closes in on, picked from the match seed
+    target: Point,
+    /// The bud whose turn just started, to be hurt if it stands in the hazard
+    starting: Option<ObjectId>,
+}
+
+impl SuddenDeath {
+    pub fn new(start_round: u32, arena: (Point, Point), seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let target = Point::new(
+            rng.random_range(arena.0.x..=arena.1.x),
+            rng.random_range(arena.0.y..=arena.1.y),
+        );
+        Self {
+            start_round,
+            rounds: 0,
+            seen: Vec::new(),
+            safe: arena,
+            arena,
+            target,
+            starting: None,
+        }
+    }
+    pub fn is_safe(&self, tile: Point) -> bool {
+        (self.safe.0.x..=self.safe.1.x).contains(&tile.x)
+            && (self.safe.0.y..=self.safe.1.y).contains(&tile.y)
+    }
+    /// The safe area one ring in, each side stops once it reaches the target
+    fn next_safe(&self) -> (Point, Point) {
+        let (min, max) = self.safe;
+        (
+            Point::new(
+                (min.x + 1).min(self.target.x),
+                (min.y + 1).min(self.target.y),
+            ),
+            Point::new(
+                (max.x - 1).max(self.target.x),
+                (max.y - 1).max(self.target.y),
+            ),
+        )
+    }
+    fn closing(&self) -> bool {
+        self.rounds >= self.start_round
+    }
+}
+
+impl EventListener for SuddenDeath {
+    fn on_event(&mut self, _turn: u32, event: &CombatEvent) {
+        if let CombatEvent::TurnStart { bud } = event {
+            if self.seen.contains(&bud.id) {
+                self.seen.clear();
+                self.rounds += 1;
+                if self.closing() {
+                    self.safe = self.next_safe();
+                }
+            }
+            self.seen.push(bud.id);
+            self.starting = Some(bud.id);
+        }
+    }
+}
+
+impl<'g> GameObject<'g> for SuddenDeath {
+    fn get_position(&self) -> Vector2d {
+        Vector2d::new(0.0, 0.0)
+    }
+
+    fn get_draw_values(&self) -> (Vector2d, Vector2d) {
+        (self.get_position(), Vector2d::new(1.0, 1.0))
+    }
+
+    /// Fills the hazard and outlines the ring that closes next
+    fn draw(&self, canvas: &mut Canvas<Window>, camera: &mut Camera) {
+        let next = self.next_safe();
+        for x in self.arena.0.x..=self.arena.1.x {
+            for y in self.arena.0.y..=self.arena.1.y {
+                let tile = Point::new(x, y);
+                let mut rect = Rect::from_center(tile, 16, 16);
+                camera.rect_to_camera(&mut rect);
+                if !self.is_safe(tile) {
+                    canvas.set_draw_color(Color::RGBA(200, 30, 30, 90));
+                    let _ = canvas.fill_rect(rect);
+                } else if self.start_round.saturating_sub(self.rounds) <= 1
+                    && !((next.0.x..=next.1.x).contains(&x) && (next.0.y..=next.1.y).contains(&y))
+                {
+                    canvas.set_draw_color(Color::RGBA(200, 30, 30, 200));
+                    let _ = canvas.draw_rect(rect);
+                }
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        _delta_time: f32,
+        collisions: &mut Collisions<'g>,
+        _gi: &mut GameInfo<'g>,
+        _si: &mut StateInfo<'g>,
+    ) -> bool {
+        let Some(id) = self.starting.take() else {
+            return true;
+        };
+        if collisions
+            .get_position(id)
+            .is_some_and(|tile| !self.is_safe(tile))
+        {
+            if let Some(bud) = collisions.get_collider(id) {
+                bud.borrow_mut().on_effected(
+                    Box::new(DamageEffect::new(HAZARD_DAMAGE)),
+                    collisions.get_colliders(),
+                );
+            }
+        }
+        true
+    }
+}
+
+impl NoButton for SuddenDeath {}
@@ -5,13 +5,12 @@ use crate::game::collision_system::collisions::Collisions;
 use crate::game::game_info::GameInfo;
 use crate::game::game_state::StateInfo;
 use crate::game::menu::menu_state::menu_states::MenuStateHandler;
+use crate::game::registry::ObjectId;
 use crate::game::scene_manager::Object;
-use crate::game::turn_system::game_modes::death_match::DeathMatch;
-use crate::game::turn_system::game_modes::GameMode;
 
 pub struct TurnHandler<'g> {
-    object_list: VecDeque<Rc<RefCell<Object<'g>>>>,
-    current: Option<Rc<RefCell<Object<'g>>>>,
+    object_list: VecDeque<(ObjectId, Rc<RefCell<Object<'g>>>)>,
+    current: Option<(ObjectId, Rc<RefCell<Object<'g>>>)>,
 }
 
 impl<'g> TurnHandler<'g> {
@@ -28,46 +27,60 @@ impl<'g> TurnHandler<'g> {
         gi: &mut GameInfo<'g>,
         si: &mut StateInfo<'g>,
         msh: &mut MenuStateHandler<'g>,
-        game_mode: &mut DeathMatch,
     ) {
         let ending = self.current.clone();
         match ending {
-            Some(val) => {
+            Some((id, val)) => {
                 let did_turn = val.borrow_mut().end(_delta_time, collisions, gi, si, msh);
-                game_mode.check_done(collisions, gi, si);
                 if did_turn {
-                    self.add(val);
+                    self.add(id, val);
                 } else {
                 }
             }
             None => {}
         }
-        let mut starting = self.object_list.pop_front().unwrap();
-        let mut can_start = starting
-            .borrow_mut()
-            .start(_delta_time, collisions, gi, si, msh);
-        while !can_start {
-            starting = self.object_list.pop_front().unwrap();
-            can_start = starting
+        si.events.borrow_mut().next_turn();
+        let (mut id, mut starting) = self.object_list.pop_front().unwrap();
+        let mut can_start = !self.skips_turn(id, &starting, collisions)
+            && starting
                 .borrow_mut()
                 .start(_delta_time, collisions, gi, si, msh);
+        while !can_start {
+            (id, starting) = self.object_list.pop_front().unwrap();
+            can_start = !self.skips_turn(id, &starting, collisions)
+                && starting
+                    .borrow_mut()
+                    .start(_delta_time, collisions, gi, si, msh);
+        }
+        self.current = Option::Some((id, starting));
+    }
+    /// Objects skipping their turn go straight to the back of the queue
+    fn skips_turn(
+        &mut self,
+        id: ObjectId,
+        starting: &Rc<RefCell<Object<'g>>>,
+        collisions: &mut Collisions<'g>,
+    ) -> bool {
+        if starting.borrow_mut().skip_turn(collisions) {
+            self.add(id, Rc::clone(starting));
+            return true;
         }
-        self.current = Option::Some(starting);
+        false
     }
-    pub fn add(&mut self, obj: Rc<RefCell<Object<'g>>>) {
-        self.object_list.push_back(obj);
+    /// Orders the queue by initiative, keeping the current order between equals
+    pub fn sort_by_initiative(&mut self) {
+        self.object_list
+            .make_contiguous()
+            .sort_by_key(|(_, obj)| -obj.borrow().initiative());
     }
-    pub fn remove(&mut self, index: usize) {
-        self.object_list.remove(index);
+    pub fn add(&mut self, id: ObjectId, obj: Rc<RefCell<Object<'g>>>) {
+        self.object_list.push_back((id, obj));
     }
-    pub fn remove_all(&mut self, indexes: &[usize], collisions: &mut Collisions) {
-        for (i, index) in indexes.iter().enumerate() {
-            // self.object_list
-            //     .get(*index - i)
-            //     .unwrap()
-            //     .borrow_mut()
-            //     .remove_collider(collisions);
-            self.object_list.remove(*index - i);
-        }
+    pub fn remove(&mut self, id: ObjectId) {
+        self.object_list.retain(|(other_id, _)| *other_id != id);
+    }
+    pub fn remove_all(&mut self, ids: &[ObjectId]) {
+        self.object_list
+            .retain(|(other_id, _)| !ids.contains(other_id));
     }
 }